@@ -60,7 +60,7 @@ const loadFixture = async () => {
 
   const contractPackageHash = await getAccountNamedKeyValue(
     accountInfo,
-    `contract_package_hash`
+    `${CONTRACT_NAME!}_package_hash`
   );
 
   console.log(`... CEP47 Contract Hash: ${contractHash}`);
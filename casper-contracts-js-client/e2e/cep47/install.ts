@@ -77,7 +77,7 @@ const test = async () => {
 
   const contractPackageHash = await getAccountNamedKeyValue(
     accountInfo,
-    `contract_package_hash`
+    `${CONTRACT_NAME!}_package_hash`
   );
   console.log(`... CEP47 Contract Package Hash: ${contractPackageHash}`);
 };
@@ -63,7 +63,7 @@ const test = async () => {
 
   const contractPackageHash = await getAccountNamedKeyValue(
     accountInfo,
-    `contract_package_hash`
+    `${CONTRACT_NAME!}_package_hash`
   );
 
   console.log(`... Contract Hash: ${contractHash}`);
@@ -82,7 +82,7 @@ const test = async () => {
 
   const contractPackageHash = await getAccountNamedKeyValue(
     accountInfo,
-    `contract_package_hash`
+    `${MARKET_CONTRACT_NAME!}_package_hash`
   );
   console.log(`... Market Contract Package Hash: ${contractPackageHash}`);
 
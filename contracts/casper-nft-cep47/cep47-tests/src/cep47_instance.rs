@@ -5,12 +5,39 @@ use blake2::{
     VarBlake2b,
 };
 use casper_types::{
-    account::AccountHash, bytesrepr::ToBytes, runtime_args, CLTyped, Key, RuntimeArgs, U256,
+    account::AccountHash,
+    bytesrepr::{self, Bytes, FromBytes, ToBytes},
+    runtime_args, CLType, CLTyped, Key, RuntimeArgs, U256,
 };
 use test_env::{TestContract, TestEnv};
 
 pub type TokenId = U256;
 pub type Meta = BTreeMap<String, String>;
+pub type MetaSchema = BTreeMap<String, (u32, String)>;
+
+/// Mirror of the Casper Event Standard `__events_schema` named key: field names and types of
+/// each event, keyed by event name.
+#[derive(Debug, PartialEq)]
+pub struct EventSchemas(pub BTreeMap<String, Vec<(String, CLType)>>);
+
+impl CLTyped for EventSchemas {
+    fn cl_type() -> CLType {
+        CLType::Map {
+            key: Box::new(CLType::String),
+            value: Box::new(CLType::List(Box::new(CLType::Tuple2([
+                Box::new(CLType::String),
+                Box::new(CLType::Any),
+            ])))),
+        }
+    }
+}
+
+impl FromBytes for EventSchemas {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (schemas, bytes) = BTreeMap::from_bytes(bytes)?;
+        Ok((EventSchemas(schemas), bytes))
+    }
+}
 
 pub struct CEP47Instance(TestContract);
 
@@ -22,6 +49,8 @@ impl CEP47Instance {
         name: &str,
         symbol: &str,
         meta: Meta,
+        max_supply: Option<U256>,
+        meta_schema: Option<MetaSchema>,
     ) -> CEP47Instance {
         CEP47Instance(TestContract::new(
             env,
@@ -31,11 +60,26 @@ impl CEP47Instance {
             runtime_args! {
                 "name" => name,
                 "symbol" => symbol,
-                "meta" => meta
+                "meta" => meta,
+                "max_supply" => max_supply,
+                "meta_schema" => meta_schema
             },
         ))
     }
 
+    /// The key safe transfers and approvals use to address this contract.
+    pub fn package_key(&self) -> Key {
+        Key::Hash(self.0.package_hash())
+    }
+
+    pub fn upgrade(&self, sender: AccountHash) {
+        self.0.upgrade(sender, "cep47-token.wasm");
+    }
+
+    pub fn migrate(&self, sender: AccountHash) {
+        self.0.call_contract(sender, "migrate", runtime_args! {});
+    }
+
     pub fn constructor(&self, sender: AccountHash, name: &str, symbol: &str, meta: Meta) {
         self.0.call_contract(
             sender,
@@ -103,6 +147,47 @@ impl CEP47Instance {
         )
     }
 
+    pub fn mint_next<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        recipient: T,
+        token_metas: Vec<Meta>,
+    ) {
+        self.0.call_contract(
+            sender,
+            "mint_next",
+            runtime_args! {
+                "recipient" => recipient.into(),
+                "token_metas" => token_metas
+            },
+        )
+    }
+
+    pub fn mint_non_transferable<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        recipient: T,
+        token_ids: Vec<TokenId>,
+        token_metas: Vec<Meta>,
+    ) {
+        self.0.call_contract(
+            sender,
+            "mint_non_transferable",
+            runtime_args! {
+                "recipient" => recipient.into(),
+                "token_ids" => token_ids,
+                "token_metas" => token_metas
+            },
+        )
+    }
+
+    pub fn is_transferable(&self, token_id: TokenId) -> bool {
+        !self
+            .0
+            .query_dictionary::<bool>("non_transferable", token_id.to_string())
+            .unwrap_or_default()
+    }
+
     pub fn burn_one<T: Into<Key>>(&self, sender: AccountHash, owner: T, token_id: TokenId) {
         self.0.call_contract(
             sender,
@@ -125,6 +210,17 @@ impl CEP47Instance {
         )
     }
 
+    pub fn admin_burn(&self, sender: AccountHash, token_ids: Vec<TokenId>, reason: &str) {
+        self.0.call_contract(
+            sender,
+            "admin_burn",
+            runtime_args! {
+                "token_ids" => token_ids,
+                "reason" => reason
+            },
+        )
+    }
+
     pub fn transfer<T: Into<Key>>(
         &self,
         sender: AccountHash,
@@ -159,6 +255,23 @@ impl CEP47Instance {
         )
     }
 
+    pub fn safe_transfer<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        recipient: T,
+        token_ids: Vec<TokenId>,
+    ) {
+        self.0.call_contract(
+            sender,
+            "safe_transfer",
+            runtime_args! {
+                "recipient" => recipient.into(),
+                "token_ids" => token_ids,
+                "data" => Bytes::new()
+            },
+        )
+    }
+
     pub fn approve<T: Into<Key>>(&self, sender: AccountHash, spender: T, token_ids: Vec<TokenId>) {
         self.0.call_contract(
             sender,
@@ -174,6 +287,56 @@ impl CEP47Instance {
         )
     }
 
+    pub fn set_approval_for_all<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        operator: T,
+        approved: bool,
+    ) {
+        self.0.call_contract(
+            sender,
+            "set_approval_for_all",
+            runtime_args! {
+                "operator" => operator.into(),
+                "approved" => approved
+            },
+        );
+    }
+
+    pub fn is_approval_for_all<T: Into<Key>>(&self, owner: T, operator: T) -> bool {
+        self.0
+            .query_dictionary(
+                "operator_approvals",
+                key_and_value_to_str(&owner.into(), &operator.into()),
+            )
+            .unwrap_or_default()
+    }
+
+    pub fn add_minter<T: Into<Key>>(&self, sender: AccountHash, minter: T) {
+        self.0.call_contract(
+            sender,
+            "add_minter",
+            runtime_args! {"minter" => minter.into()},
+        )
+    }
+
+    pub fn remove_minter<T: Into<Key>>(&self, sender: AccountHash, minter: T) {
+        self.0.call_contract(
+            sender,
+            "remove_minter",
+            runtime_args! {"minter" => minter.into()},
+        )
+    }
+
+    pub fn is_minter<T: Into<Key>>(&self, account: T) -> bool {
+        self.0
+            .query_dictionary::<()>(
+                "roles",
+                key_and_value_to_str(&account.into(), &String::from("minter")),
+            )
+            .is_some()
+    }
+
     pub fn update_token_meta(&self, sender: AccountHash, token_id: TokenId, token_meta: Meta) {
         self.0.call_contract(
             sender,
@@ -185,6 +348,37 @@ impl CEP47Instance {
         )
     }
 
+    pub fn freeze_token_meta(&self, sender: AccountHash, token_ids: Vec<TokenId>) {
+        self.0.call_contract(
+            sender,
+            "freeze_token_meta",
+            runtime_args! {"token_ids" => token_ids},
+        )
+    }
+
+    pub fn freeze_collection_meta(&self, sender: AccountHash) {
+        self.0
+            .call_contract(sender, "freeze_collection_meta", runtime_args! {})
+    }
+
+    pub fn is_token_meta_frozen(&self, token_id: TokenId) -> bool {
+        self.0
+            .query_dictionary("frozen_metadata", token_id.to_string())
+            .unwrap_or_default()
+    }
+
+    pub fn is_collection_meta_frozen(&self) -> bool {
+        self.0.query_named_key(String::from("metadata_frozen"))
+    }
+
+    pub fn add_metadata_admin<T: Into<Key>>(&self, sender: AccountHash, address: T) {
+        self.0.call_contract(
+            sender,
+            "add_metadata_admin",
+            runtime_args! {"address" => address.into()},
+        )
+    }
+
     pub fn get_token_by_index<T: Into<Key>>(&self, account: T, index: U256) -> Option<TokenId> {
         self.0.query_dictionary(
             "owned_tokens_by_index",
@@ -192,6 +386,15 @@ impl CEP47Instance {
         )
     }
 
+    pub fn token_by_index(&self, index: U256) -> Option<TokenId> {
+        self.0
+            .query_dictionary("tokens_by_index", index.to_string())
+    }
+
+    pub fn tokens_count(&self) -> U256 {
+        self.0.query_named_key(String::from("tokens_count"))
+    }
+
     pub fn balance_of<T: Into<Key>>(&self, account: T) -> U256 {
         self.0
             .query_dictionary("balances", key_to_str(&account.into()))
@@ -221,6 +424,51 @@ impl CEP47Instance {
     pub fn meta(&self) -> Meta {
         self.0.query_named_key(String::from("meta"))
     }
+
+    pub fn schema_version(&self) -> u32 {
+        self.0.query_named_key(String::from("schema_version"))
+    }
+
+    pub fn ces_events_length(&self) -> u32 {
+        self.0.query_named_key(String::from("__events_length"))
+    }
+
+    pub fn ces_event(&self, index: u32) -> Option<Bytes> {
+        self.0.query_dictionary_value("__events", index.to_string())
+    }
+
+    pub fn ces_schemas(&self) -> EventSchemas {
+        self.0.query_named_key(String::from("__events_schema"))
+    }
+
+    pub fn contract_hash(&self) -> [u8; 32] {
+        self.0.contract_hash()
+    }
+
+    pub fn set_base_uri(&self, sender: AccountHash, base_uri: &str) {
+        self.0.call_contract(
+            sender,
+            "set_base_uri",
+            runtime_args! {"base_uri" => base_uri},
+        )
+    }
+
+    pub fn freeze_base_uri(&self, sender: AccountHash) {
+        self.0
+            .call_contract(sender, "freeze_base_uri", runtime_args! {})
+    }
+
+    pub fn base_uri(&self) -> Option<String> {
+        self.0.query_named_key(String::from("base_uri"))
+    }
+
+    pub fn meta_schema(&self) -> Option<MetaSchema> {
+        self.0.query_named_key(String::from("meta_schema"))
+    }
+
+    pub fn max_supply(&self) -> Option<U256> {
+        self.0.query_named_key(String::from("max_supply"))
+    }
 }
 
 pub fn key_to_str(key: &Key) -> String {
@@ -1,15 +1,17 @@
 use std::collections::BTreeMap;
 
-use casper_types::{account::AccountHash, Key, U256};
-use test_env::TestEnv;
+use casper_types::{
+    account::AccountHash, bytesrepr::FromBytes, runtime_args, CLType, Key, RuntimeArgs, U256,
+};
+use test_env::{TestContract, TestEnv};
 
-use crate::cep47_instance::{CEP47Instance, Meta, TokenId};
+use crate::cep47_instance::{CEP47Instance, Meta, MetaSchema, TokenId};
 
 const NAME: &str = "DragonsNFT";
 const SYMBOL: &str = "DGNFT";
 
 mod meta {
-    use super::{BTreeMap, Meta};
+    use super::{BTreeMap, Meta, MetaSchema};
     pub fn contract_meta() -> Meta {
         let mut meta = BTreeMap::new();
         meta.insert("origin".to_string(), "fire".to_string());
@@ -39,12 +41,67 @@ mod meta {
         meta.insert("color".to_string(), "gold".to_string());
         meta
     }
+
+    pub fn schema() -> MetaSchema {
+        let mut schema = BTreeMap::new();
+        schema.insert("name".to_string(), (16, "".to_string()));
+        schema.insert("image".to_string(), (0, "url".to_string()));
+        schema.insert("price".to_string(), (0, "number".to_string()));
+        schema
+    }
+
+    pub fn listed_dragon() -> Meta {
+        let mut meta = BTreeMap::new();
+        meta.insert("name".to_string(), "Red Dragon".to_string());
+        meta.insert(
+            "image".to_string(),
+            "https://example.com/red.png".to_string(),
+        );
+        meta.insert("price".to_string(), "12.5".to_string());
+        meta.insert("color".to_string(), "red".to_string());
+        meta
+    }
 }
 
 fn deploy() -> (TestEnv, CEP47Instance, AccountHash) {
+    deploy_with_max_supply(None)
+}
+
+fn deploy_with_max_supply(max_supply: Option<U256>) -> (TestEnv, CEP47Instance, AccountHash) {
+    deploy_with_options(max_supply, None)
+}
+
+fn deploy_with_meta_schema(meta_schema: MetaSchema) -> (TestEnv, CEP47Instance, AccountHash) {
+    deploy_with_options(None, Some(meta_schema))
+}
+
+/// Installs a contract whose `on_cep47_received` hook answers with `ack`.
+fn deploy_receiver(env: &TestEnv, sender: AccountHash, ack: &str) -> TestContract {
+    TestContract::new(
+        env,
+        "test-receiver.wasm",
+        "receiver",
+        sender,
+        runtime_args! {"ack" => ack},
+    )
+}
+
+fn deploy_with_options(
+    max_supply: Option<U256>,
+    meta_schema: Option<MetaSchema>,
+) -> (TestEnv, CEP47Instance, AccountHash) {
     let env = TestEnv::new();
     let owner = env.next_user();
-    let token = CEP47Instance::new(&env, NAME, owner, NAME, SYMBOL, meta::contract_meta());
+    let token = CEP47Instance::new(
+        &env,
+        NAME,
+        owner,
+        NAME,
+        SYMBOL,
+        meta::contract_meta(),
+        max_supply,
+        meta_schema,
+    );
     (env, token, owner)
 }
 
@@ -55,6 +112,57 @@ fn test_deploy() {
     assert_eq!(token.symbol(), SYMBOL);
     assert_eq!(token.meta(), meta::contract_meta());
     assert_eq!(token.total_supply(), U256::zero());
+    assert_eq!(token.max_supply(), None);
+}
+
+#[test]
+fn test_installer_is_minter() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    assert!(token.is_minter(Key::Account(owner)));
+    assert!(!token.is_minter(Key::Account(user)));
+}
+
+#[test]
+#[should_panic]
+fn test_mint_without_minter_role() {
+    let (env, token, _) = deploy();
+    let user = env.next_user();
+    token.mint_one(user, user, TokenId::zero(), meta::red_dragon());
+}
+
+#[test]
+fn test_add_minter() {
+    let (env, token, owner) = deploy();
+    let minter = env.next_user();
+    let user = env.next_user();
+
+    token.add_minter(owner, minter);
+    assert!(token.is_minter(Key::Account(minter)));
+
+    token.mint_one(minter, user, TokenId::zero(), meta::red_dragon());
+    assert_eq!(token.owner_of(TokenId::zero()).unwrap(), Key::Account(user));
+}
+
+#[test]
+#[should_panic]
+fn test_add_minter_without_admin_role() {
+    let (env, token, _) = deploy();
+    let user = env.next_user();
+    token.add_minter(user, user);
+}
+
+#[test]
+#[should_panic]
+fn test_mint_after_remove_minter() {
+    let (env, token, owner) = deploy();
+    let minter = env.next_user();
+
+    token.add_minter(owner, minter);
+    token.remove_minter(owner, minter);
+    assert!(!token.is_minter(Key::Account(minter)));
+
+    token.mint_one(minter, minter, TokenId::zero(), meta::red_dragon());
 }
 
 #[test]
@@ -146,6 +254,67 @@ fn test_mint_many() {
     assert_eq!(third_user_token, None);
 }
 
+#[test]
+#[should_panic(expected = "User(3)")]
+fn test_mint_many_with_repeated_token_id() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_many(
+        owner,
+        user,
+        vec![TokenId::from(7), TokenId::from(7)],
+        vec![meta::red_dragon(), meta::blue_dragon()],
+    );
+}
+
+#[test]
+fn test_mint_next() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+
+    token.mint_one(owner, user, TokenId::one(), meta::red_dragon());
+    token.mint_next(owner, user, vec![meta::blue_dragon(), meta::black_dragon()]);
+    token.mint_next(owner, user, vec![meta::gold_dragon()]);
+
+    assert_eq!(token.total_supply(), U256::from(4));
+    assert_eq!(
+        token.token_meta(TokenId::zero()).unwrap(),
+        meta::blue_dragon()
+    );
+    assert_eq!(
+        token.token_meta(TokenId::one()).unwrap(),
+        meta::red_dragon()
+    );
+    assert_eq!(
+        token.token_meta(TokenId::from(2)).unwrap(),
+        meta::black_dragon()
+    );
+    assert_eq!(
+        token.token_meta(TokenId::from(3)).unwrap(),
+        meta::gold_dragon()
+    );
+}
+
+#[test]
+fn test_mint_up_to_max_supply() {
+    let (env, token, owner) = deploy_with_max_supply(Some(U256::from(2)));
+    let user = env.next_user();
+
+    token.mint_next(owner, user, vec![meta::red_dragon(), meta::blue_dragon()]);
+    assert_eq!(token.max_supply(), Some(U256::from(2)));
+    assert_eq!(token.total_supply(), U256::from(2));
+}
+
+#[test]
+#[should_panic]
+fn test_mint_above_max_supply() {
+    let (env, token, owner) = deploy_with_max_supply(Some(U256::from(2)));
+    let user = env.next_user();
+
+    token.mint_next(owner, user, vec![meta::red_dragon(), meta::blue_dragon()]);
+    token.mint_next(owner, user, vec![meta::gold_dragon()]);
+}
+
 #[test]
 fn test_burn_many() {
     let (env, token, owner) = deploy();
@@ -254,6 +423,50 @@ fn test_burn_one() {
     assert_eq!(new_second_user_token, None);
 }
 
+#[test]
+fn test_admin_burn() {
+    let (env, token, owner) = deploy();
+    let ali = env.next_user();
+    let bob = env.next_user();
+    token.mint_one(owner, ali, TokenId::zero(), meta::red_dragon());
+    token.mint_one(owner, bob, TokenId::one(), meta::blue_dragon());
+    token.approve(ali, bob, vec![TokenId::zero()]);
+
+    token.admin_burn(owner, vec![TokenId::zero(), TokenId::one()], "infringing");
+    assert_eq!(token.total_supply(), U256::zero());
+    assert_eq!(token.balance_of(ali), U256::zero());
+    assert_eq!(token.balance_of(bob), U256::zero());
+    assert_eq!(token.owner_of(TokenId::zero()), None);
+    assert_eq!(token.get_approved(ali, TokenId::zero()), None);
+
+    let event = token.ces_event(token.ces_events_length() - 1).unwrap();
+    let (name, rest) = String::from_bytes(&event).unwrap();
+    let (admin, rest) = Key::from_bytes(rest).unwrap();
+    let (token_ids, rest) = Vec::<TokenId>::from_bytes(rest).unwrap();
+    let (reason, _) = String::from_bytes(rest).unwrap();
+    assert_eq!(name, "event_AdminBurn");
+    assert_eq!(admin, Key::Account(owner));
+    assert_eq!(token_ids, vec![TokenId::zero(), TokenId::one()]);
+    assert_eq!(reason, "infringing");
+    let burn_events = (0..token.ces_events_length())
+        .filter(|index| {
+            let event = token.ces_event(*index).unwrap();
+            String::from_bytes(&event).unwrap().0 == "event_Burn"
+        })
+        .count();
+    assert_eq!(burn_events, 0);
+}
+
+#[test]
+#[should_panic]
+fn test_admin_burn_without_admin_role() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+
+    token.admin_burn(user, vec![TokenId::zero()], "infringing");
+}
+
 #[test]
 fn test_transfer_token() {
     let (env, token, owner) = deploy();
@@ -386,3 +599,670 @@ fn test_token_metadata_update() {
     token.update_token_meta(owner, token_id, meta::gold_dragon());
     assert_eq!(token.token_meta(token_id).unwrap(), meta::gold_dragon());
 }
+
+#[test]
+fn test_token_metadata_update_by_token_owner() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let token_id = TokenId::zero();
+
+    token.mint_one(owner, user, token_id, meta::red_dragon());
+
+    token.update_token_meta(user, token_id, meta::gold_dragon());
+    assert_eq!(token.token_meta(token_id).unwrap(), meta::gold_dragon());
+}
+
+#[test]
+#[should_panic]
+fn test_token_metadata_update_by_other_user() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let other = env.next_user();
+    let token_id = TokenId::zero();
+
+    token.mint_one(owner, user, token_id, meta::red_dragon());
+
+    token.update_token_meta(other, token_id, meta::gold_dragon());
+}
+
+#[test]
+fn test_token_metadata_update_by_metadata_admin() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let metadata_admin = env.next_user();
+    let token_id = TokenId::zero();
+
+    token.mint_one(owner, user, token_id, meta::red_dragon());
+    token.add_metadata_admin(owner, metadata_admin);
+
+    token.update_token_meta(metadata_admin, token_id, meta::gold_dragon());
+    assert_eq!(token.token_meta(token_id).unwrap(), meta::gold_dragon());
+}
+
+#[test]
+fn test_freeze_token_meta() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let token_ids = vec![TokenId::zero(), TokenId::one()];
+    token.mint_many(
+        owner,
+        user,
+        token_ids.clone(),
+        vec![meta::red_dragon(), meta::blue_dragon()],
+    );
+
+    token.freeze_token_meta(user, vec![token_ids[0]]);
+    assert!(token.is_token_meta_frozen(token_ids[0]));
+    assert!(!token.is_token_meta_frozen(token_ids[1]));
+
+    // Unfrozen tokens of the same collection stay editable.
+    token.update_token_meta(user, token_ids[1], meta::gold_dragon());
+    assert_eq!(token.token_meta(token_ids[1]).unwrap(), meta::gold_dragon());
+}
+
+#[test]
+#[should_panic]
+fn test_token_metadata_update_after_freeze() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let token_id = TokenId::zero();
+
+    token.mint_one(owner, user, token_id, meta::red_dragon());
+    token.freeze_token_meta(user, vec![token_id]);
+
+    token.update_token_meta(owner, token_id, meta::gold_dragon());
+}
+
+#[test]
+#[should_panic]
+fn test_freeze_token_meta_by_other_user() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let other = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+
+    token.freeze_token_meta(other, vec![TokenId::zero()]);
+}
+
+#[test]
+#[should_panic]
+fn test_token_metadata_update_after_collection_freeze() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let token_id = TokenId::zero();
+
+    token.mint_one(owner, user, token_id, meta::red_dragon());
+    token.freeze_collection_meta(owner);
+    assert!(token.is_collection_meta_frozen());
+
+    token.update_token_meta(user, token_id, meta::gold_dragon());
+}
+
+#[test]
+#[should_panic]
+fn test_freeze_collection_meta_without_metadata_admin_role() {
+    let (env, token, _) = deploy();
+    let user = env.next_user();
+    token.freeze_collection_meta(user);
+}
+
+#[test]
+fn test_upgrade_preserves_state() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let token_ids = vec![TokenId::zero(), TokenId::one()];
+    token.mint_many(
+        owner,
+        user,
+        token_ids.clone(),
+        vec![meta::red_dragon(), meta::blue_dragon()],
+    );
+    token.approve(user, owner, vec![token_ids[1]]);
+    let previous_contract_hash = token.contract_hash();
+
+    token.upgrade(owner);
+
+    assert_ne!(token.contract_hash(), previous_contract_hash);
+    assert_eq!(token.schema_version(), 1);
+    assert_eq!(token.total_supply(), U256::from(2));
+    assert_eq!(token.balance_of(user), U256::from(2));
+    assert_eq!(token.owner_of(token_ids[0]).unwrap(), Key::Account(user));
+    assert_eq!(
+        token.get_token_by_index(user, U256::one()).unwrap(),
+        token_ids[1]
+    );
+    assert_eq!(token.token_meta(token_ids[1]).unwrap(), meta::blue_dragon());
+    assert_eq!(
+        token.get_approved(user, token_ids[1]).unwrap(),
+        Key::Account(owner)
+    );
+
+    // The new version keeps working against the preserved state.
+    token.transfer_from(owner, user, owner, vec![token_ids[1]]);
+    assert_eq!(token.owner_of(token_ids[1]).unwrap(), Key::Account(owner));
+    token.mint_one(owner, user, TokenId::from(2), meta::gold_dragon());
+    assert_eq!(token.total_supply(), U256::from(3));
+}
+
+#[test]
+#[should_panic]
+fn test_migrate_without_admin_role() {
+    let (env, token, _) = deploy();
+    let user = env.next_user();
+    token.migrate(user);
+}
+
+#[test]
+fn test_set_approval_for_all() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let operator = env.next_user();
+    assert!(!token.is_approval_for_all(user, operator));
+
+    token.set_approval_for_all(user, operator, true);
+    assert!(token.is_approval_for_all(user, operator));
+
+    token.set_approval_for_all(user, operator, false);
+    assert!(!token.is_approval_for_all(user, operator));
+    assert!(!token.is_approval_for_all(owner, operator));
+}
+
+#[test]
+fn test_transfer_from_by_operator() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let operator = env.next_user();
+    let token_ids = vec![TokenId::zero(), TokenId::one()];
+    token.mint_many(
+        owner,
+        user,
+        token_ids.clone(),
+        vec![meta::red_dragon(), meta::blue_dragon()],
+    );
+
+    token.set_approval_for_all(user, operator, true);
+    token.transfer_from(operator, user, operator, token_ids.clone());
+
+    assert_eq!(
+        token.owner_of(token_ids[0]).unwrap(),
+        Key::Account(operator)
+    );
+    assert_eq!(
+        token.owner_of(token_ids[1]).unwrap(),
+        Key::Account(operator)
+    );
+}
+
+#[test]
+fn test_burn_by_operator() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let operator = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+
+    token.set_approval_for_all(user, operator, true);
+    token.burn_one(operator, user, TokenId::zero());
+
+    assert_eq!(token.owner_of(TokenId::zero()), None);
+    assert_eq!(token.total_supply(), U256::zero());
+}
+
+#[test]
+#[should_panic]
+fn test_transfer_from_by_revoked_operator() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let operator = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+
+    token.set_approval_for_all(user, operator, true);
+    token.set_approval_for_all(user, operator, false);
+    token.transfer_from(operator, user, operator, vec![TokenId::zero()]);
+}
+
+#[test]
+#[should_panic]
+fn test_set_approval_for_all_to_self() {
+    let (env, token, _) = deploy();
+    let user = env.next_user();
+    token.set_approval_for_all(user, user, true);
+}
+
+#[test]
+fn test_mint_with_meta_schema() {
+    let (env, token, owner) = deploy_with_meta_schema(meta::schema());
+    let user = env.next_user();
+    assert_eq!(token.meta_schema(), Some(meta::schema()));
+
+    token.mint_one(owner, user, TokenId::zero(), meta::listed_dragon());
+    assert_eq!(
+        token.token_meta(TokenId::zero()).unwrap(),
+        meta::listed_dragon()
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_mint_with_missing_schema_key() {
+    let (env, token, owner) = deploy_with_meta_schema(meta::schema());
+    let user = env.next_user();
+    let mut token_meta = meta::listed_dragon();
+    token_meta.remove("image");
+
+    token.mint_one(owner, user, TokenId::zero(), token_meta);
+}
+
+#[test]
+#[should_panic]
+fn test_mint_with_too_long_schema_value() {
+    let (env, token, owner) = deploy_with_meta_schema(meta::schema());
+    let user = env.next_user();
+    let mut token_meta = meta::listed_dragon();
+    token_meta.insert("name".to_string(), "Red Dragon of the North".to_string());
+
+    token.mint_one(owner, user, TokenId::zero(), token_meta);
+}
+
+#[test]
+#[should_panic]
+fn test_mint_copies_with_invalid_url() {
+    let (env, token, owner) = deploy_with_meta_schema(meta::schema());
+    let user = env.next_user();
+    let mut token_meta = meta::listed_dragon();
+    token_meta.insert("image".to_string(), "red.png".to_string());
+
+    token.mint_copies(
+        owner,
+        user,
+        vec![TokenId::zero(), TokenId::one()],
+        token_meta,
+        2,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_token_metadata_update_with_invalid_number() {
+    let (env, token, owner) = deploy_with_meta_schema(meta::schema());
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::listed_dragon());
+    let mut token_meta = meta::listed_dragon();
+    token_meta.insert("price".to_string(), "12.5 CSPR".to_string());
+
+    token.update_token_meta(user, TokenId::zero(), token_meta);
+}
+
+#[test]
+#[should_panic]
+fn test_deploy_with_unknown_meta_format() {
+    let mut schema = meta::schema();
+    schema.insert("date".to_string(), (0, "date".to_string()));
+    deploy_with_meta_schema(schema);
+}
+
+#[test]
+fn test_mint_in_base_uri_mode() {
+    let (env, token, owner) = deploy_with_meta_schema(meta::schema());
+    let user = env.next_user();
+    token.set_base_uri(owner, "https://example.com/dragons/");
+    assert_eq!(
+        token.base_uri(),
+        Some("https://example.com/dragons/".to_string())
+    );
+
+    // Empty maps are not stored, so `token_meta` resolves the token through the base URI.
+    token.mint_one(owner, user, TokenId::zero(), Meta::new());
+    token.mint_one(owner, user, TokenId::one(), meta::listed_dragon());
+    assert_eq!(token.token_meta(TokenId::zero()), None);
+    assert_eq!(
+        token.token_meta(TokenId::one()).unwrap(),
+        meta::listed_dragon()
+    );
+
+    token.set_base_uri(owner, "ipfs://dragons/");
+    assert_eq!(token.base_uri(), Some("ipfs://dragons/".to_string()));
+}
+
+#[test]
+#[should_panic]
+fn test_set_base_uri_without_admin_role() {
+    let (env, token, _) = deploy();
+    let user = env.next_user();
+    token.set_base_uri(user, "https://example.com/dragons/");
+}
+
+#[test]
+#[should_panic]
+fn test_set_base_uri_after_freeze() {
+    let (_, token, owner) = deploy();
+    token.set_base_uri(owner, "https://example.com/dragons/");
+    token.freeze_base_uri(owner);
+
+    token.set_base_uri(owner, "ipfs://dragons/");
+}
+
+#[test]
+#[should_panic]
+fn test_set_base_uri_after_collection_freeze() {
+    let (_, token, owner) = deploy();
+    token.set_base_uri(owner, "https://example.com/dragons/");
+    token.freeze_collection_meta(owner);
+
+    token.set_base_uri(owner, "ipfs://dragons/");
+}
+
+#[test]
+fn test_freeze_token_meta_in_base_uri_mode() {
+    let (env, token, owner) = deploy_with_meta_schema(meta::schema());
+    let user = env.next_user();
+    token.set_base_uri(owner, "https://example.com/dragons/");
+    token.mint_one(owner, user, TokenId::zero(), Meta::new());
+    token.mint_one(owner, user, TokenId::one(), Meta::new());
+
+    token.freeze_token_meta(user, vec![TokenId::zero()]);
+    token.set_base_uri(owner, "ipfs://dragons/");
+
+    let mut frozen_meta = Meta::new();
+    frozen_meta.insert(
+        "token_uri".to_string(),
+        "https://example.com/dragons/0".to_string(),
+    );
+    assert_eq!(token.token_meta(TokenId::zero()), Some(frozen_meta));
+    assert_eq!(token.token_meta(TokenId::one()), None);
+}
+
+#[test]
+fn test_token_by_index() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let token_ids = vec![TokenId::zero(), TokenId::one(), TokenId::from(2)];
+    token.mint_many(
+        owner,
+        user,
+        token_ids.clone(),
+        vec![
+            meta::red_dragon(),
+            meta::blue_dragon(),
+            meta::black_dragon(),
+        ],
+    );
+    assert_eq!(token.tokens_count(), U256::from(3));
+    assert_eq!(token.token_by_index(U256::one()), Some(token_ids[1]));
+
+    // The last token is swapped into the burnt token's slot.
+    token.burn_one(user, user, token_ids[0]);
+    assert_eq!(token.tokens_count(), U256::from(2));
+    assert_eq!(token.token_by_index(U256::zero()), Some(token_ids[2]));
+    assert_eq!(token.token_by_index(U256::one()), Some(token_ids[1]));
+    assert_eq!(token.token_by_index(U256::from(2)), None);
+
+    token.burn_one(user, user, token_ids[1]);
+    assert_eq!(token.tokens_count(), U256::one());
+    assert_eq!(token.token_by_index(U256::zero()), Some(token_ids[2]));
+    assert_eq!(token.token_by_index(U256::one()), None);
+}
+
+#[test]
+fn test_transfer_clears_approval() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let spender = env.next_user();
+    let recipient = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.approve(user, spender, vec![TokenId::zero()]);
+
+    token.transfer(user, recipient, vec![TokenId::zero()]);
+
+    assert_eq!(token.get_approved(user, TokenId::zero()), None);
+    assert_eq!(token.get_approved(recipient, TokenId::zero()), None);
+}
+
+#[test]
+fn test_transfer_from_clears_approval() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let spender = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.approve(user, spender, vec![TokenId::zero()]);
+
+    token.transfer_from(spender, user, spender, vec![TokenId::zero()]);
+
+    assert_eq!(token.get_approved(user, TokenId::zero()), None);
+}
+
+#[test]
+fn test_burn_clears_approval() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let spender = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.approve(user, spender, vec![TokenId::zero()]);
+
+    token.burn_one(user, user, TokenId::zero());
+
+    assert_eq!(token.get_approved(user, TokenId::zero()), None);
+}
+
+#[test]
+#[should_panic]
+fn test_stale_approval_after_token_returns() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let spender = env.next_user();
+    let recipient = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.approve(user, spender, vec![TokenId::zero()]);
+
+    // The token comes back to its previous owner, but the earlier approval must not.
+    token.transfer(user, recipient, vec![TokenId::zero()]);
+    token.transfer(recipient, user, vec![TokenId::zero()]);
+
+    token.transfer_from(spender, user, spender, vec![TokenId::zero()]);
+}
+
+#[test]
+fn test_safe_transfer_to_account() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let recipient = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+
+    token.safe_transfer(user, recipient, vec![TokenId::zero()]);
+
+    assert_eq!(
+        token.owner_of(TokenId::zero()).unwrap(),
+        Key::Account(recipient)
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_safe_transfer_to_contract_without_receiver_hook() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    let other_token = CEP47Instance::new(
+        &env,
+        "other_cep47",
+        owner,
+        NAME,
+        SYMBOL,
+        meta::contract_meta(),
+        None,
+        None,
+    );
+
+    token.safe_transfer(user, other_token.package_key(), vec![TokenId::zero()]);
+}
+
+#[test]
+fn test_safe_transfer_to_receiver() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    let receiver = deploy_receiver(&env, owner, "cep47_received");
+    let receiver_key = Key::Hash(receiver.package_hash());
+
+    token.safe_transfer(user, receiver_key, vec![TokenId::zero()]);
+
+    assert_eq!(token.owner_of(TokenId::zero()).unwrap(), receiver_key);
+}
+
+#[test]
+#[should_panic]
+fn test_safe_transfer_to_receiver_with_wrong_ack() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    let receiver = deploy_receiver(&env, owner, "rejected");
+
+    token.safe_transfer(
+        user,
+        Key::Hash(receiver.package_hash()),
+        vec![TokenId::zero()],
+    );
+}
+
+#[test]
+fn test_burn_non_transferable_token() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    token.mint_non_transferable(owner, user, vec![TokenId::zero()], vec![meta::red_dragon()]);
+    assert!(!token.is_transferable(TokenId::zero()));
+    assert_eq!(token.owner_of(TokenId::zero()).unwrap(), Key::Account(user));
+
+    token.burn_one(user, user, TokenId::zero());
+    assert_eq!(token.owner_of(TokenId::zero()), None);
+
+    // The id can be reused for a regular token.
+    token.mint_one(owner, user, TokenId::zero(), meta::blue_dragon());
+    assert!(token.is_transferable(TokenId::zero()));
+}
+
+#[test]
+#[should_panic]
+fn test_transfer_non_transferable_token() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let recipient = env.next_user();
+    token.mint_non_transferable(owner, user, vec![TokenId::zero()], vec![meta::red_dragon()]);
+
+    token.transfer(user, recipient, vec![TokenId::zero()]);
+}
+
+#[test]
+#[should_panic]
+fn test_approve_non_transferable_token() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let spender = env.next_user();
+    token.mint_non_transferable(owner, user, vec![TokenId::zero()], vec![meta::red_dragon()]);
+
+    token.approve(user, spender, vec![TokenId::zero()]);
+}
+
+#[test]
+#[should_panic]
+fn test_transfer_from_non_transferable_token_by_operator() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let operator = env.next_user();
+    token.mint_non_transferable(owner, user, vec![TokenId::zero()], vec![meta::red_dragon()]);
+    token.set_approval_for_all(user, operator, true);
+
+    token.transfer_from(operator, user, operator, vec![TokenId::zero()]);
+}
+
+#[test]
+fn test_events_are_logged_in_order() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let recipient = env.next_user();
+    assert_eq!(token.ces_events_length(), 0);
+
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.approve(user, owner, vec![TokenId::zero()]);
+    token.transfer(user, recipient, vec![TokenId::zero()]);
+
+    let event_names: Vec<String> = (0..token.ces_events_length())
+        .map(|index| {
+            String::from_bytes(&token.ces_event(index).unwrap())
+                .unwrap()
+                .0
+        })
+        .collect();
+    assert_eq!(
+        event_names,
+        vec![
+            "event_Mint",
+            "event_Approve",
+            "event_ApprovalRevoked",
+            "event_Transfer"
+        ]
+    );
+    assert_eq!(token.ces_event(token.ces_events_length()), None);
+}
+
+#[test]
+fn test_logged_event_payload() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let token_ids = vec![TokenId::zero(), TokenId::one()];
+    token.mint_many(
+        owner,
+        user,
+        token_ids.clone(),
+        vec![meta::red_dragon(), meta::blue_dragon()],
+    );
+
+    let event = token.ces_event(0).unwrap();
+    let (name, rest) = String::from_bytes(&event).unwrap();
+    let (recipient, rest) = Key::from_bytes(rest).unwrap();
+    let (logged_ids, rest) = Vec::<TokenId>::from_bytes(rest).unwrap();
+    assert_eq!(name, "event_Mint");
+    assert_eq!(recipient, Key::Account(user));
+    assert_eq!(logged_ids, token_ids);
+    assert!(rest.is_empty());
+}
+
+#[test]
+fn test_ces_schemas_registered_at_install() {
+    let (_, token, _) = deploy();
+    let schemas = token.ces_schemas().0;
+
+    assert_eq!(token.ces_events_length(), 0);
+    assert_eq!(
+        schemas["Transfer"],
+        vec![
+            ("sender".to_string(), CLType::Key),
+            ("recipient".to_string(), CLType::Key),
+            (
+                "token_ids".to_string(),
+                CLType::List(Box::new(CLType::U256))
+            ),
+        ]
+    );
+    assert!(schemas["BaseUriFrozen"].is_empty());
+    assert_eq!(schemas.len(), 12);
+}
+
+#[test]
+fn test_ces_event_encoding() {
+    let (env, token, owner) = deploy();
+    let user = env.next_user();
+    let recipient = env.next_user();
+    token.mint_one(owner, user, TokenId::zero(), meta::red_dragon());
+    token.transfer(user, recipient, vec![TokenId::zero()]);
+
+    assert_eq!(token.ces_events_length(), 2);
+    let event = token.ces_event(1).unwrap();
+    let (name, rest) = String::from_bytes(&event).unwrap();
+    let (sender, rest) = Key::from_bytes(rest).unwrap();
+    let (logged_recipient, rest) = Key::from_bytes(rest).unwrap();
+    let (token_ids, rest) = Vec::<TokenId>::from_bytes(rest).unwrap();
+    assert_eq!(name, "event_Transfer");
+    assert_eq!(sender, Key::Account(user));
+    assert_eq!(logged_recipient, Key::Account(recipient));
+    assert_eq!(token_ids, vec![TokenId::zero()]);
+    assert!(rest.is_empty());
+}
@@ -10,11 +10,15 @@ use casper_contract::{
     unwrap_or_revert::UnwrapOrRevert,
 };
 use casper_types::{
-    runtime_args, CLType, CLTyped, CLValue, ContractPackageHash, EntryPoint, EntryPointAccess,
-    EntryPointType, EntryPoints, Group, Key, Parameter, RuntimeArgs, URef, U256,
+    bytesrepr::Bytes, contracts::NamedKeys, runtime_args, CLType, CLTyped, CLValue, ContractHash,
+    ContractPackageHash, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Group, Key,
+    Parameter, RuntimeArgs, URef, U256,
 };
-use cep47::{Meta, TokenId, CEP47};
-use contract_utils::{ContractContext, OnChainContractStorage};
+use cep47::{
+    data::{METADATA_ADMIN_ROLE, MINTER_ROLE},
+    Meta, MetaSchema, TokenId, CEP47,
+};
+use contract_utils::{AdminControl, ContractContext, OnChainContractStorage, RoleControl};
 
 #[derive(Default)]
 struct NFTToken(OnChainContractStorage);
@@ -26,9 +30,26 @@ impl ContractContext<OnChainContractStorage> for NFTToken {
 }
 
 impl CEP47<OnChainContractStorage> for NFTToken {}
+impl AdminControl<OnChainContractStorage> for NFTToken {}
+impl RoleControl<OnChainContractStorage> for NFTToken {}
 impl NFTToken {
-    fn constructor(&mut self, name: String, symbol: String, meta: Meta) {
-        CEP47::init(self, name, symbol, meta);
+    fn constructor(
+        &mut self,
+        name: String,
+        symbol: String,
+        meta: Meta,
+        max_supply: Option<U256>,
+        meta_schema: Option<MetaSchema>,
+    ) {
+        CEP47::init(self, name, symbol, meta, max_supply, meta_schema).unwrap_or_revert();
+        AdminControl::init(self);
+        RoleControl::init(self);
+
+        // The installer administers the collection and is its first minter and metadata admin.
+        let installer = self.get_caller();
+        self.add_admin_without_checked(installer);
+        self.grant_role_without_checked(MINTER_ROLE, installer);
+        self.grant_role_without_checked(METADATA_ADMIN_ROLE, installer);
     }
 }
 
@@ -37,7 +58,9 @@ fn constructor() {
     let name = runtime::get_named_arg::<String>("name");
     let symbol = runtime::get_named_arg::<String>("symbol");
     let meta = runtime::get_named_arg::<Meta>("meta");
-    NFTToken::default().constructor(name, symbol, meta);
+    let max_supply = runtime::get_named_arg::<Option<U256>>("max_supply");
+    let meta_schema = runtime::get_named_arg::<Option<MetaSchema>>("meta_schema");
+    NFTToken::default().constructor(name, symbol, meta, max_supply, meta_schema);
 }
 
 #[no_mangle]
@@ -64,6 +87,18 @@ fn total_supply() {
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
+#[no_mangle]
+fn max_supply() {
+    let ret = NFTToken::default().max_supply();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn meta_schema() {
+    let ret = NFTToken::default().meta_schema();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
 #[no_mangle]
 fn balance_of() {
     let owner = runtime::get_named_arg::<Key>("owner");
@@ -79,6 +114,21 @@ fn get_token_by_index() {
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
+#[no_mangle]
+fn token_by_index() {
+    let index = runtime::get_named_arg::<U256>("index");
+    let ret = NFTToken::default().token_by_index(index);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn tokens() {
+    let offset = runtime::get_named_arg::<u64>("offset");
+    let limit = runtime::get_named_arg::<u64>("limit");
+    let ret = NFTToken::default().tokens(offset, limit);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
 #[no_mangle]
 fn owner_of() {
     let token_id = runtime::get_named_arg::<TokenId>("token_id");
@@ -93,17 +143,86 @@ fn token_meta() {
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
+#[no_mangle]
+fn base_uri() {
+    let ret = NFTToken::default().base_uri();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn set_base_uri() {
+    let base_uri = runtime::get_named_arg::<String>("base_uri");
+    let mut token = NFTToken::default();
+    token.assert_caller_is_admin();
+    token.set_base_uri(base_uri).unwrap_or_revert();
+}
+
+#[no_mangle]
+fn freeze_base_uri() {
+    let mut token = NFTToken::default();
+    token.assert_caller_is_admin();
+    token.freeze_base_uri();
+}
+
+#[no_mangle]
+fn is_base_uri_frozen() {
+    let ret = NFTToken::default().is_base_uri_frozen();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
 #[no_mangle]
 fn update_token_meta() {
     let token_id = runtime::get_named_arg::<TokenId>("token_id");
     let token_meta = runtime::get_named_arg::<Meta>("token_meta");
-    NFTToken::default()
-        .set_token_meta(token_id, token_meta)
-        .unwrap_or_revert();
+    let mut token = NFTToken::default();
+    // Metadata admins may rewrite any token; everyone else only the tokens they own.
+    if token.has_role(METADATA_ADMIN_ROLE, token.get_caller()) {
+        token
+            .set_token_meta_internal(token_id, token_meta)
+            .unwrap_or_revert();
+    } else {
+        token
+            .set_token_meta(token_id, token_meta)
+            .unwrap_or_revert();
+    }
+}
+
+#[no_mangle]
+fn freeze_token_meta() {
+    let token_ids = runtime::get_named_arg::<Vec<TokenId>>("token_ids");
+    let mut token = NFTToken::default();
+    if token.has_role(METADATA_ADMIN_ROLE, token.get_caller()) {
+        token
+            .freeze_token_meta_internal(token_ids)
+            .unwrap_or_revert();
+    } else {
+        token.freeze_token_meta(token_ids).unwrap_or_revert();
+    }
+}
+
+#[no_mangle]
+fn freeze_collection_meta() {
+    let mut token = NFTToken::default();
+    token.assert_caller_has_role(METADATA_ADMIN_ROLE);
+    token.freeze_collection_meta();
+}
+
+#[no_mangle]
+fn is_token_meta_frozen() {
+    let token_id = runtime::get_named_arg::<TokenId>("token_id");
+    let ret = NFTToken::default().is_token_meta_frozen(token_id);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn is_collection_meta_frozen() {
+    let ret = NFTToken::default().is_collection_meta_frozen();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
 #[no_mangle]
 fn mint() {
+    NFTToken::default().assert_caller_has_role(MINTER_ROLE);
     let recipient = runtime::get_named_arg::<Key>("recipient");
     let token_ids = runtime::get_named_arg::<Vec<TokenId>>("token_ids");
     let token_metas = runtime::get_named_arg::<Vec<Meta>>("token_metas");
@@ -114,6 +233,7 @@ fn mint() {
 
 #[no_mangle]
 fn mint_copies() {
+    NFTToken::default().assert_caller_has_role(MINTER_ROLE);
     let recipient = runtime::get_named_arg::<Key>("recipient");
     let token_ids = runtime::get_named_arg::<Vec<U256>>("token_ids");
     let token_meta = runtime::get_named_arg::<Meta>("token_meta");
@@ -123,6 +243,35 @@ fn mint_copies() {
         .unwrap_or_revert();
 }
 
+#[no_mangle]
+fn mint_next() {
+    NFTToken::default().assert_caller_has_role(MINTER_ROLE);
+    let recipient = runtime::get_named_arg::<Key>("recipient");
+    let token_metas = runtime::get_named_arg::<Vec<Meta>>("token_metas");
+    let ret = NFTToken::default()
+        .mint_next(recipient, token_metas)
+        .unwrap_or_revert();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn mint_non_transferable() {
+    NFTToken::default().assert_caller_has_role(MINTER_ROLE);
+    let recipient = runtime::get_named_arg::<Key>("recipient");
+    let token_ids = runtime::get_named_arg::<Vec<TokenId>>("token_ids");
+    let token_metas = runtime::get_named_arg::<Vec<Meta>>("token_metas");
+    NFTToken::default()
+        .mint_non_transferable(recipient, token_ids, token_metas)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+fn is_transferable() {
+    let token_id = runtime::get_named_arg::<TokenId>("token_id");
+    let ret = NFTToken::default().is_transferable(token_id);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
 #[no_mangle]
 fn burn() {
     let owner = runtime::get_named_arg::<Key>("owner");
@@ -132,6 +281,15 @@ fn burn() {
         .unwrap_or_revert();
 }
 
+#[no_mangle]
+fn admin_burn() {
+    let token_ids = runtime::get_named_arg::<Vec<TokenId>>("token_ids");
+    let reason = runtime::get_named_arg::<String>("reason");
+    let mut token = NFTToken::default();
+    token.assert_caller_is_admin();
+    token.admin_burn(token_ids, reason).unwrap_or_revert();
+}
+
 #[no_mangle]
 fn transfer() {
     let recipient = runtime::get_named_arg::<Key>("recipient");
@@ -151,6 +309,27 @@ fn transfer_from() {
         .unwrap_or_revert();
 }
 
+#[no_mangle]
+fn safe_transfer() {
+    let recipient = runtime::get_named_arg::<Key>("recipient");
+    let token_ids = runtime::get_named_arg::<Vec<TokenId>>("token_ids");
+    let data = runtime::get_named_arg::<Bytes>("data");
+    NFTToken::default()
+        .safe_transfer(recipient, token_ids, data)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+fn safe_transfer_from() {
+    let sender = runtime::get_named_arg::<Key>("sender");
+    let recipient = runtime::get_named_arg::<Key>("recipient");
+    let token_ids = runtime::get_named_arg::<Vec<TokenId>>("token_ids");
+    let data = runtime::get_named_arg::<Bytes>("data");
+    NFTToken::default()
+        .safe_transfer_from(sender, recipient, token_ids, data)
+        .unwrap_or_revert();
+}
+
 #[no_mangle]
 fn approve() {
     let spender = runtime::get_named_arg::<Key>("spender");
@@ -168,30 +347,110 @@ fn get_approved() {
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
+#[no_mangle]
+fn set_approval_for_all() {
+    let operator = runtime::get_named_arg::<Key>("operator");
+    let approved = runtime::get_named_arg::<bool>("approved");
+    NFTToken::default()
+        .set_approval_for_all(operator, approved)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+fn is_approval_for_all() {
+    let owner = runtime::get_named_arg::<Key>("owner");
+    let operator = runtime::get_named_arg::<Key>("operator");
+    let ret = NFTToken::default().is_approval_for_all(owner, operator);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn add_minter() {
+    let minter = runtime::get_named_arg::<Key>("minter");
+    NFTToken::default().grant_role(MINTER_ROLE, minter);
+}
+
+#[no_mangle]
+fn remove_minter() {
+    let minter = runtime::get_named_arg::<Key>("minter");
+    NFTToken::default().revoke_role(MINTER_ROLE, minter);
+}
+
+#[no_mangle]
+fn is_minter() {
+    let address = runtime::get_named_arg::<Key>("address");
+    let ret = NFTToken::default().has_role(MINTER_ROLE, address);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn add_metadata_admin() {
+    let address = runtime::get_named_arg::<Key>("address");
+    NFTToken::default().grant_role(METADATA_ADMIN_ROLE, address);
+}
+
+#[no_mangle]
+fn remove_metadata_admin() {
+    let address = runtime::get_named_arg::<Key>("address");
+    NFTToken::default().revoke_role(METADATA_ADMIN_ROLE, address);
+}
+
+#[no_mangle]
+fn is_metadata_admin() {
+    let address = runtime::get_named_arg::<Key>("address");
+    let ret = NFTToken::default().has_role(METADATA_ADMIN_ROLE, address);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn migrate() {
+    let mut token = NFTToken::default();
+    token.assert_caller_is_admin();
+    token.migrate().unwrap_or_revert();
+}
+
+#[no_mangle]
+fn schema_version() {
+    let ret = NFTToken::default().schema_version();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
 #[no_mangle]
 fn call() {
+    let contract_name: String = runtime::get_named_arg("contract_name");
+    // Running the session again against an existing package adds a new version to it.
+    match runtime::get_key(&format!("{}_package_hash", contract_name)) {
+        Some(package_key) => upgrade(contract_name, package_key),
+        None => install(contract_name),
+    }
+}
+
+fn install(contract_name: String) {
     // Read arguments for the constructor call.
     let name: String = runtime::get_named_arg("name");
     let symbol: String = runtime::get_named_arg("symbol");
     let meta: Meta = runtime::get_named_arg("meta");
-    let contract_name: String = runtime::get_named_arg("contract_name");
+    let max_supply: Option<U256> = runtime::get_named_arg("max_supply");
+    let meta_schema: Option<MetaSchema> = runtime::get_named_arg("meta_schema");
 
     // Prepare constructor args
     let constructor_args = runtime_args! {
         "name" => name,
         "symbol" => symbol,
-        "meta" => meta
+        "meta" => meta,
+        "max_supply" => max_supply,
+        "meta_schema" => meta_schema
     };
 
     let (contract_hash, _) = storage::new_contract(
         get_entry_points(),
         None,
-        Some(String::from("contract_package_hash")),
-        None,
+        Some(format!("{}_package_hash", contract_name)),
+        Some(format!("{}_access_uref", contract_name)),
     );
 
     let package_hash: ContractPackageHash = ContractPackageHash::new(
-        runtime::get_key("contract_package_hash")
+        runtime::get_key(&format!("{}_package_hash", contract_name))
             .unwrap_or_revert()
             .into_hash()
             .unwrap_or_revert(),
@@ -210,6 +469,30 @@ fn call() {
     storage::remove_contract_user_group_urefs(package_hash, "constructor", urefs)
         .unwrap_or_revert();
 
+    put_contract_hash_keys(&contract_name, contract_hash);
+}
+
+fn upgrade(contract_name: String, package_key: Key) {
+    let package_hash = ContractPackageHash::new(package_key.into_hash().unwrap_or_revert());
+    let previous_contract_hash = ContractHash::new(
+        runtime::get_key(&format!("{}_contract_hash", contract_name))
+            .unwrap_or_revert()
+            .into_hash()
+            .unwrap_or_revert(),
+    );
+
+    // The new version inherits the previous version's named keys, so the owners, owned tokens,
+    // metadata and allowances dictionaries and the total supply stay in place.
+    let (contract_hash, _) =
+        storage::add_contract_version(package_hash, get_entry_points(), NamedKeys::new());
+    storage::disable_contract_version(package_hash, previous_contract_hash).unwrap_or_revert();
+
+    let _: () = runtime::call_contract(contract_hash, "migrate", runtime_args! {});
+
+    put_contract_hash_keys(&contract_name, contract_hash);
+}
+
+fn put_contract_hash_keys(contract_name: &str, contract_hash: ContractHash) {
     runtime::put_key(
         &format!("{}_contract_hash", contract_name),
         contract_hash.into(),
@@ -228,6 +511,8 @@ fn get_entry_points() -> EntryPoints {
             Parameter::new("name", String::cl_type()),
             Parameter::new("symbol", String::cl_type()),
             Parameter::new("meta", Meta::cl_type()),
+            Parameter::new("max_supply", Option::<U256>::cl_type()),
+            Parameter::new("meta_schema", Option::<MetaSchema>::cl_type()),
         ],
         <()>::cl_type(),
         EntryPointAccess::Groups(vec![Group::new("constructor")]),
@@ -261,6 +546,20 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "max_supply",
+        vec![],
+        Option::<U256>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "meta_schema",
+        vec![],
+        Option::<MetaSchema>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "balance_of",
         vec![Parameter::new("owner", Key::cl_type())],
@@ -282,6 +581,34 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "base_uri",
+        vec![],
+        Option::<String>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_base_uri",
+        vec![Parameter::new("base_uri", String::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "freeze_base_uri",
+        vec![],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "is_base_uri_frozen",
+        vec![],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "update_token_meta",
         vec![
@@ -292,6 +619,37 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "freeze_token_meta",
+        vec![Parameter::new(
+            "token_ids",
+            CLType::List(Box::new(TokenId::cl_type())),
+        )],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "freeze_collection_meta",
+        vec![],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "is_token_meta_frozen",
+        vec![Parameter::new("token_id", TokenId::cl_type())],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "is_collection_meta_frozen",
+        vec![],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "mint",
         vec![
@@ -315,6 +673,34 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "mint_next",
+        vec![
+            Parameter::new("recipient", Key::cl_type()),
+            Parameter::new("token_metas", CLType::List(Box::new(Meta::cl_type()))),
+        ],
+        CLType::List(Box::new(TokenId::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "mint_non_transferable",
+        vec![
+            Parameter::new("recipient", Key::cl_type()),
+            Parameter::new("token_ids", CLType::List(Box::new(TokenId::cl_type()))),
+            Parameter::new("token_metas", CLType::List(Box::new(Meta::cl_type()))),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "is_transferable",
+        vec![Parameter::new("token_id", TokenId::cl_type())],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "burn",
         vec![
@@ -325,6 +711,16 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "admin_burn",
+        vec![
+            Parameter::new("token_ids", CLType::List(Box::new(TokenId::cl_type()))),
+            Parameter::new("reason", String::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "transfer",
         vec![
@@ -346,6 +742,29 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "safe_transfer",
+        vec![
+            Parameter::new("recipient", Key::cl_type()),
+            Parameter::new("token_ids", CLType::List(Box::new(TokenId::cl_type()))),
+            Parameter::new("data", Bytes::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "safe_transfer_from",
+        vec![
+            Parameter::new("sender", Key::cl_type()),
+            Parameter::new("recipient", Key::cl_type()),
+            Parameter::new("token_ids", CLType::List(Box::new(TokenId::cl_type()))),
+            Parameter::new("data", Bytes::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "approve",
         vec![
@@ -376,5 +795,98 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "token_by_index",
+        vec![Parameter::new("index", U256::cl_type())],
+        CLType::Option(Box::new(TokenId::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "tokens",
+        vec![
+            Parameter::new("offset", u64::cl_type()),
+            Parameter::new("limit", u64::cl_type()),
+        ],
+        CLType::List(Box::new(TokenId::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_approval_for_all",
+        vec![
+            Parameter::new("operator", Key::cl_type()),
+            Parameter::new("approved", bool::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "is_approval_for_all",
+        vec![
+            Parameter::new("owner", Key::cl_type()),
+            Parameter::new("operator", Key::cl_type()),
+        ],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "migrate",
+        vec![],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "schema_version",
+        vec![],
+        u32::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "add_minter",
+        vec![Parameter::new("minter", Key::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "remove_minter",
+        vec![Parameter::new("minter", Key::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "is_minter",
+        vec![Parameter::new("address", Key::cl_type())],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "add_metadata_admin",
+        vec![Parameter::new("address", Key::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "remove_metadata_admin",
+        vec![Parameter::new("address", Key::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "is_metadata_admin",
+        vec![Parameter::new("address", Key::cl_type())],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points
 }
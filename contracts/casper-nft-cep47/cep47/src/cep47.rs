@@ -1,11 +1,20 @@
 use crate::{
-    data::{self, Allowances, Metadata, OwnedTokens, Owners},
+    data::{
+        self, AllTokens, Allowances, FrozenMetadata, Metadata, NonTransferable, OperatorApprovals,
+        OwnedTokens, Owners,
+    },
     event::CEP47Event,
-    Meta, TokenId,
+    meta_schema, Meta, MetaSchema, TokenId,
 };
-use alloc::{string::String, vec::Vec};
-use casper_types::{ApiError, Key, U256};
-use contract_utils::{ContractContext, ContractStorage};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use casper_types::{
+    bytesrepr::Bytes, runtime_args, ApiError, ContractPackageHash, Key, RuntimeArgs, U256,
+};
+use contract_utils::{CesEvents, ContractContext, ContractStorage};
 use core::convert::TryInto;
 
 #[repr(u16)]
@@ -14,8 +23,23 @@ pub enum Error {
     WrongArguments = 2,
     TokenIdAlreadyExists = 3,
     TokenIdDoesntExist = 4,
+    MaxSupplyReached = 5,
+    UnsupportedSchemaVersion = 6,
+    MetadataFrozen = 7,
+    InvalidMetadata = 8,
+    BaseUriFrozen = 9,
+    TransferRejected = 10,
+    NonTransferable = 11,
 }
 
+/// Entry point a contract must expose to receive tokens through `safe_transfer`.
+pub const ON_CEP47_RECEIVED: &str = "on_cep47_received";
+/// Value `on_cep47_received` returns to accept the tokens.
+pub const CEP47_RECEIVED_ACK: &str = "cep47_received";
+
+/// Upper bound on the number of token ids a single paginated read returns.
+pub const MAX_PAGE_SIZE: u64 = 50;
+
 impl From<Error> for ApiError {
     fn from(error: Error) -> ApiError {
         ApiError::User(error as u16)
@@ -23,15 +47,54 @@ impl From<Error> for ApiError {
 }
 
 pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
-    fn init(&mut self, name: String, symbol: String, meta: Meta) {
+    fn init(
+        &mut self,
+        name: String,
+        symbol: String,
+        meta: Meta,
+        max_supply: Option<U256>,
+        meta_schema: Option<MetaSchema>,
+    ) -> Result<(), Error> {
+        if let Some(meta_schema) = &meta_schema {
+            if !meta_schema::is_valid_schema(meta_schema) {
+                return Err(Error::WrongArguments);
+            }
+        }
         data::set_name(name);
         data::set_symbol(symbol);
         data::set_meta(meta);
         data::set_total_supply(U256::zero());
+        data::set_max_supply(max_supply);
+        data::set_meta_schema(meta_schema);
+        data::set_next_token_id(TokenId::zero());
+        data::set_schema_version(data::CURRENT_SCHEMA_VERSION);
         Owners::init();
         OwnedTokens::init();
         Metadata::init();
         Allowances::init();
+        OperatorApprovals::init();
+        FrozenMetadata::init();
+        AllTokens::init();
+        NonTransferable::init();
+        CesEvents::init(CEP47Event::schemas());
+        Ok(())
+    }
+
+    /// Brings storage written by an earlier contract version up to the current schema.
+    /// Dictionaries and named keys carry over to new versions, so only layout changes need work.
+    fn migrate(&mut self) -> Result<(), Error> {
+        // Version 1 is the first upgradeable layout. The baseline package kept no access URef,
+        // so it can't reach this entry point and has to be reinstalled instead.
+        if data::schema_version() != data::CURRENT_SCHEMA_VERSION {
+            return Err(Error::UnsupportedSchemaVersion);
+        }
+        // Republished on every upgrade so the schemas track the events this build emits.
+        CesEvents::init(CEP47Event::schemas());
+        Ok(())
+    }
+
+    fn schema_version(&self) -> u32 {
+        data::schema_version()
     }
 
     fn name(&self) -> String {
@@ -50,6 +113,23 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
         data::total_supply()
     }
 
+    fn max_supply(&self) -> Option<U256> {
+        data::max_supply()
+    }
+
+    fn meta_schema(&self) -> Option<MetaSchema> {
+        data::meta_schema()
+    }
+
+    fn validate_token_meta(&self, meta: &Meta) -> Result<(), Error> {
+        match data::meta_schema() {
+            Some(meta_schema) if !meta_schema::validate(&meta_schema, meta) => {
+                Err(Error::InvalidMetadata)
+            }
+            _ => Ok(()),
+        }
+    }
+
     fn balance_of(&self, owner: Key) -> U256 {
         OwnedTokens::instance().get_balances(&owner)
     }
@@ -58,14 +138,63 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
         Owners::instance().get(&token_id)
     }
 
+    /// Returns the token's own metadata or, for tokens minted without it, a `token_uri` built
+    /// from the collection's base URI.
     fn token_meta(&self, token_id: TokenId) -> Option<Meta> {
-        Metadata::instance().get(&token_id)
+        if let Some(meta) = Metadata::instance().get(&token_id) {
+            return Some(meta);
+        }
+        self.owner_of(token_id)?;
+        let base_uri = data::base_uri()?;
+        let mut meta = Meta::new();
+        meta.insert("token_uri".to_string(), base_uri + &token_id.to_string());
+        Some(meta)
+    }
+
+    fn base_uri(&self) -> Option<String> {
+        data::base_uri()
+    }
+
+    fn set_base_uri(&mut self, base_uri: String) -> Result<(), Error> {
+        if data::base_uri_frozen() {
+            return Err(Error::BaseUriFrozen);
+        }
+        // The base URI is part of every token's metadata, so it freezes with the collection.
+        if data::metadata_frozen() {
+            return Err(Error::MetadataFrozen);
+        }
+        data::set_base_uri(Some(base_uri.clone()));
+        self.emit(CEP47Event::BaseUriUpdate { base_uri });
+        Ok(())
+    }
+
+    fn is_base_uri_frozen(&self) -> bool {
+        data::base_uri_frozen()
+    }
+
+    /// Makes the base URI permanently immutable.
+    fn freeze_base_uri(&mut self) {
+        data::set_base_uri_frozen(true);
+        self.emit(CEP47Event::BaseUriFrozen);
     }
 
     fn set_token_meta(&mut self, token_id: TokenId, meta: Meta) -> Result<(), Error> {
+        match self.owner_of(token_id) {
+            None => return Err(Error::TokenIdDoesntExist),
+            Some(owner) if owner != self.get_caller() => return Err(Error::PermissionDenied),
+            Some(_) => {}
+        }
+        self.set_token_meta_internal(token_id, meta)
+    }
+
+    fn set_token_meta_internal(&mut self, token_id: TokenId, meta: Meta) -> Result<(), Error> {
         if self.owner_of(token_id).is_none() {
             return Err(Error::TokenIdDoesntExist);
         };
+        if self.is_token_meta_frozen(token_id) {
+            return Err(Error::MetadataFrozen);
+        }
+        self.validate_token_meta(&meta)?;
 
         let metadata_dict = Metadata::instance();
         metadata_dict.set(&token_id, meta);
@@ -74,10 +203,79 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
         Ok(())
     }
 
+    fn is_token_meta_frozen(&self, token_id: TokenId) -> bool {
+        data::metadata_frozen() || FrozenMetadata::instance().get(&token_id)
+    }
+
+    fn freeze_token_meta(&mut self, token_ids: Vec<TokenId>) -> Result<(), Error> {
+        let caller = self.get_caller();
+        for token_id in &token_ids {
+            match self.owner_of(*token_id) {
+                None => return Err(Error::TokenIdDoesntExist),
+                Some(owner) if owner != caller => return Err(Error::PermissionDenied),
+                Some(_) => {}
+            }
+        }
+        self.freeze_token_meta_internal(token_ids)
+    }
+
+    /// Makes the metadata of `token_ids` permanently immutable. There is no way to unfreeze.
+    fn freeze_token_meta_internal(&mut self, token_ids: Vec<TokenId>) -> Result<(), Error> {
+        for token_id in &token_ids {
+            if self.owner_of(*token_id).is_none() {
+                return Err(Error::TokenIdDoesntExist);
+            }
+        }
+
+        // Tokens resolved through the base URI keep the URI they have now.
+        let metadata_dict = Metadata::instance();
+        let frozen_metadata_dict = FrozenMetadata::instance();
+        for token_id in &token_ids {
+            if metadata_dict.get(token_id).is_none() {
+                if let Some(meta) = self.token_meta(*token_id) {
+                    metadata_dict.set(token_id, meta);
+                }
+            }
+            frozen_metadata_dict.set(token_id);
+        }
+
+        self.emit(CEP47Event::MetadataFrozen { token_ids });
+        Ok(())
+    }
+
+    fn is_collection_meta_frozen(&self) -> bool {
+        data::metadata_frozen()
+    }
+
+    /// Makes the metadata of every current and future token permanently immutable.
+    fn freeze_collection_meta(&mut self) {
+        data::set_metadata_frozen(true);
+        self.emit(CEP47Event::CollectionMetadataFrozen);
+    }
+
     fn get_token_by_index(&self, owner: Key, index: U256) -> Option<TokenId> {
         OwnedTokens::instance().get_token_by_index(&owner, &index)
     }
 
+    fn token_by_index(&self, index: U256) -> Option<TokenId> {
+        AllTokens::instance().get_token_by_index(&index)
+    }
+
+    fn tokens(&self, offset: u64, limit: u64) -> Vec<TokenId> {
+        let all_tokens = AllTokens::instance();
+        let start = U256::from(offset);
+        let end = all_tokens
+            .len()
+            .min(start + U256::from(limit.min(MAX_PAGE_SIZE)));
+        let mut token_ids = Vec::new();
+        let mut index = start;
+        while index < end {
+            token_ids.push(all_tokens.get_token_by_index(&index).unwrap_or_revert());
+            index += U256::one();
+        }
+        token_ids
+    }
+
     fn validate_token_ids(&self, token_ids: Vec<TokenId>) -> bool {
         for token_id in &token_ids {
             if self.owner_of(*token_id).is_some() {
@@ -97,26 +295,44 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
             return Err(Error::WrongArguments);
         };
 
-        for token_id in &token_ids {
-            if self.owner_of(*token_id).is_some() {
+        // An id repeated within the batch would be minted twice, so it counts as existing too.
+        for (index, token_id) in token_ids.iter().enumerate() {
+            if self.owner_of(*token_id).is_some() || token_ids[..index].contains(token_id) {
                 return Err(Error::TokenIdAlreadyExists);
             }
         }
+        // Under a base URI, tokens minted with an empty map store nothing and resolve to their URI.
+        let base_uri_mode = data::base_uri().is_some();
+        for token_meta in &token_metas {
+            if !(base_uri_mode && token_meta.is_empty()) {
+                self.validate_token_meta(token_meta)?;
+            }
+        }
+
+        let minted_tokens_count: U256 = From::<u64>::from(token_ids.len().try_into().unwrap());
+        let new_total_supply = data::total_supply()
+            .checked_add(minted_tokens_count)
+            .unwrap();
+        if let Some(max_supply) = data::max_supply() {
+            if new_total_supply > max_supply {
+                return Err(Error::MaxSupplyReached);
+            }
+        }
 
         let owners_dict = Owners::instance();
         let owned_tokens_dict = OwnedTokens::instance();
         let metadata_dict = Metadata::instance();
+        let all_tokens = AllTokens::instance();
 
         for (token_id, token_meta) in token_ids.iter().zip(&token_metas) {
-            metadata_dict.set(token_id, token_meta.clone());
+            if !(base_uri_mode && token_meta.is_empty()) {
+                metadata_dict.set(token_id, token_meta.clone());
+            }
             owners_dict.set(token_id, recipient);
             owned_tokens_dict.set_token(&recipient, token_id);
+            all_tokens.add_token(token_id);
         }
 
-        let minted_tokens_count: U256 = From::<u64>::from(token_ids.len().try_into().unwrap());
-        let new_total_supply = data::total_supply()
-            .checked_add(minted_tokens_count)
-            .unwrap();
         data::set_total_supply(new_total_supply);
 
         self.emit(CEP47Event::Mint {
@@ -137,6 +353,39 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
         self.mint(recipient, token_ids, token_metas)
     }
 
+    fn mint_next(&mut self, recipient: Key, token_metas: Vec<Meta>) -> Result<Vec<TokenId>, Error> {
+        // Ids picked by callers of `mint` may already occupy the counter's range, so skip them.
+        let mut next_token_id = data::next_token_id();
+        let mut token_ids = Vec::with_capacity(token_metas.len());
+        while token_ids.len() < token_metas.len() {
+            if self.owner_of(next_token_id).is_none() {
+                token_ids.push(next_token_id);
+            }
+            next_token_id += TokenId::one();
+        }
+        data::set_next_token_id(next_token_id);
+        self.mint(recipient, token_ids, token_metas)
+    }
+
+    /// Mints tokens that can never leave `recipient`; they can only be burned.
+    fn mint_non_transferable(
+        &mut self,
+        recipient: Key,
+        token_ids: Vec<TokenId>,
+        token_metas: Vec<Meta>,
+    ) -> Result<Vec<TokenId>, Error> {
+        let token_ids = self.mint(recipient, token_ids, token_metas)?;
+        let non_transferable_dict = NonTransferable::instance();
+        for token_id in &token_ids {
+            non_transferable_dict.set(token_id);
+        }
+        Ok(token_ids)
+    }
+
+    fn is_transferable(&self, token_id: TokenId) -> bool {
+        !NonTransferable::instance().get(&token_id)
+    }
+
     fn burn(&mut self, owner: Key, token_ids: Vec<TokenId>) -> Result<(), Error> {
         let spender = self.get_caller();
         if spender != owner {
@@ -146,16 +395,21 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
                 }
             }
         }
-        self.burn_internal(owner, token_ids)
+        self.burn_internal(owner, &token_ids)?;
+        self.emit(CEP47Event::Burn { owner, token_ids });
+        Ok(())
     }
 
-    fn burn_internal(&mut self, owner: Key, token_ids: Vec<TokenId>) -> Result<(), Error> {
+    /// Removes `owner`'s `token_ids`. Callers emit the event that records why.
+    fn burn_internal(&mut self, owner: Key, token_ids: &[TokenId]) -> Result<(), Error> {
         let owners_dict = Owners::instance();
         let owned_tokens_dict = OwnedTokens::instance();
         let metadata_dict = Metadata::instance();
-        let allowances_dict = Allowances::instance();
+        let frozen_metadata_dict = FrozenMetadata::instance();
+        let all_tokens = AllTokens::instance();
+        let non_transferable_dict = NonTransferable::instance();
 
-        for token_id in &token_ids {
+        for token_id in token_ids {
             match owners_dict.get(token_id) {
                 Some(owner_of_key) => {
                     if owner_of_key != owner {
@@ -168,11 +422,14 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
             }
         }
 
-        for token_id in &token_ids {
+        self.clear_approvals(owner, token_ids);
+        for token_id in token_ids {
             owned_tokens_dict.remove_token(&owner, token_id);
             metadata_dict.remove(token_id);
             owners_dict.remove(token_id);
-            allowances_dict.remove(&owner, token_id);
+            frozen_metadata_dict.remove(token_id);
+            all_tokens.remove_token(token_id);
+            non_transferable_dict.remove(token_id);
         }
 
         let burnt_tokens_count: U256 = From::<u64>::from(token_ids.len().try_into().unwrap());
@@ -180,8 +437,24 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
             .checked_sub(burnt_tokens_count)
             .unwrap();
         data::set_total_supply(new_total_supply);
+        Ok(())
+    }
 
-        self.emit(CEP47Event::Burn { owner, token_ids });
+    /// Burns `token_ids` regardless of who owns them, recording `reason` so the removal can be
+    /// audited. Meant for moderating content minted into a shared collection.
+    fn admin_burn(&mut self, token_ids: Vec<TokenId>, reason: String) -> Result<(), Error> {
+        for token_id in &token_ids {
+            let owner = match self.owner_of(*token_id) {
+                Some(owner) => owner,
+                None => return Err(Error::TokenIdDoesntExist),
+            };
+            self.burn_internal(owner, &[*token_id])?;
+        }
+        self.emit(CEP47Event::AdminBurn {
+            admin: self.get_caller(),
+            token_ids,
+            reason,
+        });
         Ok(())
     }
 
@@ -191,6 +464,7 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
             match self.owner_of(*token_id) {
                 None => return Err(Error::WrongArguments),
                 Some(owner) if owner != caller => return Err(Error::PermissionDenied),
+                Some(_) if !self.is_transferable(*token_id) => return Err(Error::NonTransferable),
                 Some(_) => Allowances::instance().set(&caller, token_id, spender),
             }
         }
@@ -206,6 +480,24 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
         Allowances::instance().get(&owner, &token_id)
     }
 
+    fn set_approval_for_all(&mut self, operator: Key, approved: bool) -> Result<(), Error> {
+        let caller = self.get_caller();
+        if operator == caller {
+            return Err(Error::WrongArguments);
+        }
+        OperatorApprovals::instance().set(&caller, &operator, approved);
+        self.emit(CEP47Event::ApprovalForAll {
+            owner: caller,
+            operator,
+            approved,
+        });
+        Ok(())
+    }
+
+    fn is_approval_for_all(&self, owner: Key, operator: Key) -> bool {
+        OperatorApprovals::instance().get(&owner, &operator)
+    }
+
     fn transfer(&mut self, recipient: Key, token_ids: Vec<TokenId>) -> Result<(), Error> {
         self.transfer_from(self.get_caller(), recipient, token_ids)
     }
@@ -219,17 +511,54 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
         let spender = self.get_caller();
 
         if owner != spender {
-            let allowances_dict = Allowances::instance();
             for token_id in &token_ids {
                 if !self.is_approved(owner, *token_id, spender) {
                     return Err(Error::PermissionDenied);
                 }
-                allowances_dict.remove(&owner, token_id);
             }
         }
         self.transfer_from_internal(owner, recipient, token_ids)
     }
 
+    fn safe_transfer(
+        &mut self,
+        recipient: Key,
+        token_ids: Vec<TokenId>,
+        data: Bytes,
+    ) -> Result<(), Error> {
+        self.safe_transfer_from(self.get_caller(), recipient, token_ids, data)
+    }
+
+    /// Like `transfer_from`, but a contract recipient has to accept the tokens through its
+    /// `on_cep47_received` entry point.
+    fn safe_transfer_from(
+        &mut self,
+        owner: Key,
+        recipient: Key,
+        token_ids: Vec<TokenId>,
+        data: Bytes,
+    ) -> Result<(), Error> {
+        self.transfer_from(owner, recipient, token_ids.clone())?;
+        if let Key::Hash(package_hash) = recipient {
+            // A contract without the hook makes the call itself revert.
+            let ack: String = runtime::call_versioned_contract(
+                ContractPackageHash::new(package_hash),
+                None,
+                ON_CEP47_RECEIVED,
+                runtime_args! {
+                    "operator" => self.get_caller(),
+                    "from" => owner,
+                    "token_ids" => token_ids,
+                    "data" => data
+                },
+            );
+            if ack != CEP47_RECEIVED_ACK {
+                return Err(Error::TransferRejected);
+            }
+        }
+        Ok(())
+    }
+
     fn transfer_from_internal(
         &mut self,
         owner: Key,
@@ -250,8 +579,12 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
                     return Err(Error::TokenIdDoesntExist);
                 }
             }
+            if !self.is_transferable(*token_id) {
+                return Err(Error::NonTransferable);
+            }
         }
 
+        self.clear_approvals(owner, &token_ids);
         for token_id in &token_ids {
             owned_tokens_dict.remove_token(&owner, token_id);
             owned_tokens_dict.set_token(&recipient, token_id);
@@ -266,7 +599,29 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
         Ok(())
     }
 
+    /// Drops the single-token approvals `owner` granted on `token_ids` before they change hands.
+    /// Operator approvals belong to the owner rather than the token and stay in place.
+    fn clear_approvals(&mut self, owner: Key, token_ids: &[TokenId]) {
+        let allowances_dict = Allowances::instance();
+        let mut revoked_token_ids = Vec::new();
+        for token_id in token_ids {
+            if allowances_dict.get(&owner, token_id).is_some() {
+                allowances_dict.remove(&owner, token_id);
+                revoked_token_ids.push(*token_id);
+            }
+        }
+        if !revoked_token_ids.is_empty() {
+            self.emit(CEP47Event::ApprovalRevoked {
+                owner,
+                token_ids: revoked_token_ids,
+            });
+        }
+    }
+
     fn is_approved(&self, owner: Key, token_id: TokenId, spender: Key) -> bool {
+        if self.is_approval_for_all(owner, spender) {
+            return true;
+        }
         let allowances_dict = Allowances::instance();
         if let Some(spender_of) = allowances_dict.get(&owner, &token_id) {
             if spender_of == spender {
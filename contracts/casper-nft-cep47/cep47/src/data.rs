@@ -1,29 +1,38 @@
-use alloc::{
-    collections::BTreeMap,
-    string::{String, ToString},
-    vec::Vec,
-};
-use casper_contract::{
-    contract_api::{runtime::get_call_stack, storage},
-    unwrap_or_revert::UnwrapOrRevert,
-};
-use casper_types::{system::CallStackElement, ContractPackageHash, Key, URef, U256};
-use contract_utils::{get_key, key_and_value_to_str, key_to_str, set_key, Dict};
-
-use crate::{event::CEP47Event, Meta, TokenId};
+use alloc::string::{String, ToString};
+use casper_contract::{contract_api::runtime::get_call_stack, unwrap_or_revert::UnwrapOrRevert};
+use casper_types::{system::CallStackElement, ContractPackageHash, Key, U256};
+use contract_utils::{get_key, key_and_value_to_str, key_to_str, set_key, CesEvents, Dict};
+
+use crate::{event::CEP47Event, Meta, MetaSchema, TokenId};
 
 const BALANCES_DICT: &str = "balances";
 pub const ALLOWANCES_DICT: &str = "allowances";
+pub const OPERATOR_APPROVALS_DICT: &str = "operator_approvals";
+pub const FROZEN_METADATA_DICT: &str = "frozen_metadata";
+pub const NON_TRANSFERABLE_DICT: &str = "non_transferable";
 const METADATA_DICT: &str = "metadata";
 const OWNERS_DICT: &str = "owners";
 const OWNED_TOKENS_BY_INDEX_DICT: &str = "owned_tokens_by_index";
 const OWNED_INDEXES_BY_TOKEN_DICT: &str = "owned_indexes_by_token";
-const CONTRACT_PACKAGE_HASH: &str = "contract_package_hash";
+const TOKENS_BY_INDEX_DICT: &str = "tokens_by_index";
+const INDEXES_BY_TOKEN_DICT: &str = "indexes_by_token";
 
 pub const NAME: &str = "name";
 pub const META: &str = "meta";
 pub const SYMBOL: &str = "symbol";
 pub const TOTAL_SUPPLY: &str = "total_supply";
+pub const MAX_SUPPLY: &str = "max_supply";
+pub const META_SCHEMA: &str = "meta_schema";
+pub const BASE_URI: &str = "base_uri";
+pub const BASE_URI_FROZEN: &str = "base_uri_frozen";
+pub const NEXT_TOKEN_ID: &str = "next_token_id";
+pub const TOKENS_COUNT: &str = "tokens_count";
+pub const METADATA_FROZEN: &str = "metadata_frozen";
+pub const SCHEMA_VERSION: &str = "schema_version";
+/// Storage layout version written by this build of the contract.
+pub const CURRENT_SCHEMA_VERSION: u32 = 1;
+pub const MINTER_ROLE: &str = "minter";
+pub const METADATA_ADMIN_ROLE: &str = "metadata_admin";
 
 pub struct Owners {
     dict: Dict,
@@ -159,6 +168,76 @@ impl OwnedTokens {
     }
 }
 
+/// Enumerates every existing token, independent of its owner.
+pub struct AllTokens {
+    tokens_dict: Dict,
+    indexes_dict: Dict,
+}
+
+impl AllTokens {
+    pub fn instance() -> AllTokens {
+        AllTokens {
+            tokens_dict: Dict::instance(TOKENS_BY_INDEX_DICT),
+            indexes_dict: Dict::instance(INDEXES_BY_TOKEN_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(TOKENS_BY_INDEX_DICT);
+        Dict::init(INDEXES_BY_TOKEN_DICT);
+    }
+
+    pub fn len(&self) -> U256 {
+        get_key(TOKENS_COUNT).unwrap_or_default()
+    }
+
+    fn set_len(&self, length: U256) {
+        set_key(TOKENS_COUNT, length);
+    }
+
+    pub fn get_token_by_index(&self, index: &U256) -> Option<TokenId> {
+        self.tokens_dict.get(&index.to_string())
+    }
+
+    pub fn get_index_by_token(&self, value: &TokenId) -> Option<U256> {
+        self.indexes_dict.get(&value.to_string())
+    }
+
+    pub fn add_token(&self, value: &TokenId) {
+        let length = self.len();
+        self.indexes_dict.set(&value.to_string(), length);
+        self.tokens_dict.set(&length.to_string(), *value);
+        self.set_len(length + 1);
+    }
+
+    /// Removes `value` by moving the last token into its slot. Tokens minted before the index
+    /// existed and never backfilled are ignored.
+    pub fn remove_token(&self, value: &TokenId) {
+        let length = self.len();
+        let index = match self.get_index_by_token(value) {
+            Some(index) => index,
+            None => return,
+        };
+        match length.cmp(&(index + 1)) {
+            core::cmp::Ordering::Equal => {
+                self.tokens_dict
+                    .remove::<TokenId>(&(length - 1).to_string());
+                self.set_len(length - 1);
+            }
+            core::cmp::Ordering::Greater => {
+                let last = self.get_token_by_index(&(length - 1)).unwrap_or_revert();
+                self.indexes_dict.set(&last.to_string(), index);
+                self.tokens_dict.set(&index.to_string(), last);
+                self.tokens_dict
+                    .remove::<TokenId>(&(length - 1).to_string());
+                self.set_len(length - 1);
+            }
+            core::cmp::Ordering::Less => {}
+        }
+        self.indexes_dict.remove::<U256>(&value.to_string());
+    }
+}
+
 pub struct Allowances {
     dict: Dict,
 }
@@ -196,6 +275,93 @@ impl Allowances {
     }
 }
 
+pub struct OperatorApprovals {
+    dict: Dict,
+}
+
+impl OperatorApprovals {
+    pub fn instance() -> OperatorApprovals {
+        OperatorApprovals {
+            dict: Dict::instance(OPERATOR_APPROVALS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(OPERATOR_APPROVALS_DICT)
+    }
+
+    pub fn get(&self, owner: &Key, operator: &Key) -> bool {
+        self.dict
+            .get_by_keys::<bool>((owner, operator))
+            .unwrap_or_default()
+    }
+
+    pub fn set(&self, owner: &Key, operator: &Key, approved: bool) {
+        if approved {
+            self.dict.set_by_keys((owner, operator), true);
+        } else {
+            self.dict.remove_by_vec_of_keys::<bool>((owner, operator));
+        }
+    }
+}
+
+pub struct FrozenMetadata {
+    dict: Dict,
+}
+
+impl FrozenMetadata {
+    pub fn instance() -> FrozenMetadata {
+        FrozenMetadata {
+            dict: Dict::instance(FROZEN_METADATA_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(FROZEN_METADATA_DICT)
+    }
+
+    pub fn get(&self, key: &TokenId) -> bool {
+        self.dict.get(&key.to_string()).unwrap_or_default()
+    }
+
+    pub fn set(&self, key: &TokenId) {
+        self.dict.set(&key.to_string(), true);
+    }
+
+    pub fn remove(&self, key: &TokenId) {
+        self.dict.remove::<bool>(&key.to_string());
+    }
+}
+
+/// Tokens that can only be burned, never transferred or approved.
+pub struct NonTransferable {
+    dict: Dict,
+}
+
+impl NonTransferable {
+    pub fn instance() -> NonTransferable {
+        NonTransferable {
+            dict: Dict::instance(NON_TRANSFERABLE_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(NON_TRANSFERABLE_DICT)
+    }
+
+    pub fn get(&self, key: &TokenId) -> bool {
+        self.dict.get(&key.to_string()).unwrap_or_default()
+    }
+
+    pub fn set(&self, key: &TokenId) {
+        self.dict.set(&key.to_string(), true);
+    }
+
+    pub fn remove(&self, key: &TokenId) {
+        self.dict.remove::<bool>(&key.to_string());
+    }
+}
+
 pub fn name() -> String {
     get_key(NAME).unwrap_or_revert()
 }
@@ -228,6 +394,62 @@ pub fn set_total_supply(total_supply: U256) {
     set_key(TOTAL_SUPPLY, total_supply);
 }
 
+pub fn max_supply() -> Option<U256> {
+    get_key(MAX_SUPPLY).unwrap_or_default()
+}
+
+pub fn set_max_supply(max_supply: Option<U256>) {
+    set_key(MAX_SUPPLY, max_supply);
+}
+
+pub fn meta_schema() -> Option<MetaSchema> {
+    get_key(META_SCHEMA).unwrap_or_default()
+}
+
+pub fn set_meta_schema(meta_schema: Option<MetaSchema>) {
+    set_key(META_SCHEMA, meta_schema);
+}
+
+pub fn base_uri() -> Option<String> {
+    get_key(BASE_URI).unwrap_or_default()
+}
+
+pub fn set_base_uri(base_uri: Option<String>) {
+    set_key(BASE_URI, base_uri);
+}
+
+pub fn base_uri_frozen() -> bool {
+    get_key(BASE_URI_FROZEN).unwrap_or_default()
+}
+
+pub fn set_base_uri_frozen(frozen: bool) {
+    set_key(BASE_URI_FROZEN, frozen);
+}
+
+pub fn next_token_id() -> TokenId {
+    get_key(NEXT_TOKEN_ID).unwrap_or_default()
+}
+
+pub fn set_next_token_id(token_id: TokenId) {
+    set_key(NEXT_TOKEN_ID, token_id);
+}
+
+pub fn metadata_frozen() -> bool {
+    get_key(METADATA_FROZEN).unwrap_or_default()
+}
+
+pub fn set_metadata_frozen(frozen: bool) {
+    set_key(METADATA_FROZEN, frozen);
+}
+
+pub fn schema_version() -> u32 {
+    get_key(SCHEMA_VERSION).unwrap_or_default()
+}
+
+pub fn set_schema_version(schema_version: u32) {
+    set_key(SCHEMA_VERSION, schema_version);
+}
+
 pub fn contract_package_hash() -> ContractPackageHash {
     let call_stacks = get_call_stack();
     let last_entry = call_stacks.last().unwrap_or_revert();
@@ -241,72 +463,9 @@ pub fn contract_package_hash() -> ContractPackageHash {
     package_hash.unwrap_or_revert()
 }
 
+/// Records `event` in the Casper Event Standard log, the contract's event record of reference.
 pub fn emit(event: &CEP47Event) {
-    let mut events = Vec::new();
-    let package = contract_package_hash();
-    match event {
-        CEP47Event::Mint {
-            recipient,
-            token_ids,
-        } => {
-            for token_id in token_ids {
-                let mut param = BTreeMap::new();
-                param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
-                param.insert("event_type", "cep47_mint_one".to_string());
-                param.insert("recipient", recipient.to_string());
-                param.insert("token_id", token_id.to_string());
-                events.push(param);
-            }
-        }
-        CEP47Event::Burn { owner, token_ids } => {
-            for token_id in token_ids {
-                let mut param = BTreeMap::new();
-                param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
-                param.insert("event_type", "cep47_burn_one".to_string());
-                param.insert("owner", owner.to_string());
-                param.insert("token_id", token_id.to_string());
-                events.push(param);
-            }
-        }
-        CEP47Event::Approve {
-            owner,
-            spender,
-            token_ids,
-        } => {
-            for token_id in token_ids {
-                let mut param = BTreeMap::new();
-                param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
-                param.insert("event_type", "cep47_approve_token".to_string());
-                param.insert("owner", owner.to_string());
-                param.insert("spender", spender.to_string());
-                param.insert("token_id", token_id.to_string());
-                events.push(param);
-            }
-        }
-        CEP47Event::Transfer {
-            sender,
-            recipient,
-            token_ids,
-        } => {
-            for token_id in token_ids {
-                let mut param = BTreeMap::new();
-                param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
-                param.insert("event_type", "cep47_transfer_token".to_string());
-                param.insert("sender", sender.to_string());
-                param.insert("recipient", recipient.to_string());
-                param.insert("token_id", token_id.to_string());
-                events.push(param);
-            }
-        }
-        CEP47Event::MetadataUpdate { token_id } => {
-            let mut param = BTreeMap::new();
-            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
-            param.insert("event_type", "cep47_metadata_update".to_string());
-            param.insert("token_id", token_id.to_string());
-            events.push(param);
-        }
-    };
-    for param in events {
-        let _: URef = storage::new_uref(param);
-    }
+    #[cfg(feature = "legacy-events")]
+    crate::legacy_event::emit(event);
+    CesEvents::instance().emit(event.name(), event);
 }
@@ -1,5 +1,9 @@
-use alloc::vec::Vec;
-use casper_types::Key;
+use alloc::{string::String, vec::Vec};
+use casper_types::{
+    bytesrepr::{self, ToBytes},
+    Key,
+};
+use contract_utils::{Schema, Schemas};
 
 use crate::TokenId;
 
@@ -12,17 +16,245 @@ pub enum CEP47Event {
         owner: Key,
         token_ids: Vec<TokenId>,
     },
+    AdminBurn {
+        admin: Key,
+        token_ids: Vec<TokenId>,
+        reason: String,
+    },
     Approve {
         owner: Key,
         spender: Key,
         token_ids: Vec<TokenId>,
     },
+    ApprovalRevoked {
+        owner: Key,
+        token_ids: Vec<TokenId>,
+    },
     Transfer {
         sender: Key,
         recipient: Key,
         token_ids: Vec<TokenId>,
     },
+    ApprovalForAll {
+        owner: Key,
+        operator: Key,
+        approved: bool,
+    },
     MetadataUpdate {
         token_id: TokenId,
     },
+    MetadataFrozen {
+        token_ids: Vec<TokenId>,
+    },
+    CollectionMetadataFrozen,
+    BaseUriUpdate {
+        base_uri: String,
+    },
+    BaseUriFrozen,
+}
+
+impl CEP47Event {
+    /// Name the event is registered under in the Casper Event Standard schemas.
+    pub fn name(&self) -> &'static str {
+        match self {
+            CEP47Event::Mint { .. } => "Mint",
+            CEP47Event::Burn { .. } => "Burn",
+            CEP47Event::AdminBurn { .. } => "AdminBurn",
+            CEP47Event::Approve { .. } => "Approve",
+            CEP47Event::ApprovalRevoked { .. } => "ApprovalRevoked",
+            CEP47Event::Transfer { .. } => "Transfer",
+            CEP47Event::ApprovalForAll { .. } => "ApprovalForAll",
+            CEP47Event::MetadataUpdate { .. } => "MetadataUpdate",
+            CEP47Event::MetadataFrozen { .. } => "MetadataFrozen",
+            CEP47Event::CollectionMetadataFrozen => "CollectionMetadataFrozen",
+            CEP47Event::BaseUriUpdate { .. } => "BaseUriUpdate",
+            CEP47Event::BaseUriFrozen => "BaseUriFrozen",
+        }
+    }
+
+    pub fn schemas() -> Schemas {
+        Schemas::new()
+            .with(
+                "Mint",
+                Schema::new()
+                    .with_elem::<Key>("recipient")
+                    .with_elem::<Vec<TokenId>>("token_ids"),
+            )
+            .with(
+                "Burn",
+                Schema::new()
+                    .with_elem::<Key>("owner")
+                    .with_elem::<Vec<TokenId>>("token_ids"),
+            )
+            .with(
+                "AdminBurn",
+                Schema::new()
+                    .with_elem::<Key>("admin")
+                    .with_elem::<Vec<TokenId>>("token_ids")
+                    .with_elem::<String>("reason"),
+            )
+            .with(
+                "Approve",
+                Schema::new()
+                    .with_elem::<Key>("owner")
+                    .with_elem::<Key>("spender")
+                    .with_elem::<Vec<TokenId>>("token_ids"),
+            )
+            .with(
+                "ApprovalRevoked",
+                Schema::new()
+                    .with_elem::<Key>("owner")
+                    .with_elem::<Vec<TokenId>>("token_ids"),
+            )
+            .with(
+                "Transfer",
+                Schema::new()
+                    .with_elem::<Key>("sender")
+                    .with_elem::<Key>("recipient")
+                    .with_elem::<Vec<TokenId>>("token_ids"),
+            )
+            .with(
+                "ApprovalForAll",
+                Schema::new()
+                    .with_elem::<Key>("owner")
+                    .with_elem::<Key>("operator")
+                    .with_elem::<bool>("approved"),
+            )
+            .with(
+                "MetadataUpdate",
+                Schema::new().with_elem::<TokenId>("token_id"),
+            )
+            .with(
+                "MetadataFrozen",
+                Schema::new().with_elem::<Vec<TokenId>>("token_ids"),
+            )
+            .with("CollectionMetadataFrozen", Schema::new())
+            .with(
+                "BaseUriUpdate",
+                Schema::new().with_elem::<String>("base_uri"),
+            )
+            .with("BaseUriFrozen", Schema::new())
+    }
+}
+
+/// Encodes the variant's fields in declaration order; the variant itself is identified by
+/// [`CEP47Event::name`].
+impl ToBytes for CEP47Event {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        match self {
+            CEP47Event::Mint {
+                recipient,
+                token_ids,
+            } => {
+                result.append(&mut recipient.to_bytes()?);
+                result.append(&mut token_ids.to_bytes()?);
+            }
+            CEP47Event::Burn { owner, token_ids }
+            | CEP47Event::ApprovalRevoked { owner, token_ids } => {
+                result.append(&mut owner.to_bytes()?);
+                result.append(&mut token_ids.to_bytes()?);
+            }
+            CEP47Event::AdminBurn {
+                admin,
+                token_ids,
+                reason,
+            } => {
+                result.append(&mut admin.to_bytes()?);
+                result.append(&mut token_ids.to_bytes()?);
+                result.append(&mut reason.to_bytes()?);
+            }
+            CEP47Event::Approve {
+                owner,
+                spender,
+                token_ids,
+            } => {
+                result.append(&mut owner.to_bytes()?);
+                result.append(&mut spender.to_bytes()?);
+                result.append(&mut token_ids.to_bytes()?);
+            }
+            CEP47Event::Transfer {
+                sender,
+                recipient,
+                token_ids,
+            } => {
+                result.append(&mut sender.to_bytes()?);
+                result.append(&mut recipient.to_bytes()?);
+                result.append(&mut token_ids.to_bytes()?);
+            }
+            CEP47Event::ApprovalForAll {
+                owner,
+                operator,
+                approved,
+            } => {
+                result.append(&mut owner.to_bytes()?);
+                result.append(&mut operator.to_bytes()?);
+                result.append(&mut approved.to_bytes()?);
+            }
+            CEP47Event::MetadataUpdate { token_id } => {
+                result.append(&mut token_id.to_bytes()?);
+            }
+            CEP47Event::MetadataFrozen { token_ids } => {
+                result.append(&mut token_ids.to_bytes()?);
+            }
+            CEP47Event::BaseUriUpdate { base_uri } => {
+                result.append(&mut base_uri.to_bytes()?);
+            }
+            CEP47Event::CollectionMetadataFrozen | CEP47Event::BaseUriFrozen => {}
+        }
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        match self {
+            CEP47Event::Mint {
+                recipient,
+                token_ids,
+            } => recipient.serialized_length() + token_ids.serialized_length(),
+            CEP47Event::Burn { owner, token_ids }
+            | CEP47Event::ApprovalRevoked { owner, token_ids } => {
+                owner.serialized_length() + token_ids.serialized_length()
+            }
+            CEP47Event::AdminBurn {
+                admin,
+                token_ids,
+                reason,
+            } => {
+                admin.serialized_length()
+                    + token_ids.serialized_length()
+                    + reason.serialized_length()
+            }
+            CEP47Event::Approve {
+                owner,
+                spender,
+                token_ids,
+            } => {
+                owner.serialized_length()
+                    + spender.serialized_length()
+                    + token_ids.serialized_length()
+            }
+            CEP47Event::Transfer {
+                sender,
+                recipient,
+                token_ids,
+            } => {
+                sender.serialized_length()
+                    + recipient.serialized_length()
+                    + token_ids.serialized_length()
+            }
+            CEP47Event::ApprovalForAll {
+                owner,
+                operator,
+                approved,
+            } => {
+                owner.serialized_length()
+                    + operator.serialized_length()
+                    + approved.serialized_length()
+            }
+            CEP47Event::MetadataUpdate { token_id } => token_id.serialized_length(),
+            CEP47Event::MetadataFrozen { token_ids } => token_ids.serialized_length(),
+            CEP47Event::BaseUriUpdate { base_uri } => base_uri.serialized_length(),
+            CEP47Event::CollectionMetadataFrozen | CEP47Event::BaseUriFrozen => 0,
+        }
+    }
 }
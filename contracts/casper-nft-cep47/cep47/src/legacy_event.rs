@@ -0,0 +1,148 @@
+//! Events in the string map format of the original CEP-47 contract. Nothing on chain reads
+//! them; `CEP47EventParser` in the JS client picks them out of the deploy's `WriteCLValue`
+//! transforms, so they are written while the `legacy-events` feature is on, as it is by
+//! default. The Casper Event Standard log is the record of reference.
+use alloc::{collections::BTreeMap, string::ToString, vec::Vec};
+use casper_contract::contract_api::storage;
+use casper_types::URef;
+
+use crate::{data::contract_package_hash, event::CEP47Event};
+
+const CONTRACT_PACKAGE_HASH: &str = "contract_package_hash";
+
+/// Writes each event as a string map to a fresh unnamed URef, one per token.
+pub fn emit(event: &CEP47Event) {
+    let mut events = Vec::new();
+    let package = contract_package_hash();
+    match event {
+        CEP47Event::Mint {
+            recipient,
+            token_ids,
+        } => {
+            for token_id in token_ids {
+                let mut param = BTreeMap::new();
+                param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+                param.insert("event_type", "cep47_mint_one".to_string());
+                param.insert("recipient", recipient.to_string());
+                param.insert("token_id", token_id.to_string());
+                events.push(param);
+            }
+        }
+        CEP47Event::Burn { owner, token_ids } => {
+            for token_id in token_ids {
+                let mut param = BTreeMap::new();
+                param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+                param.insert("event_type", "cep47_burn_one".to_string());
+                param.insert("owner", owner.to_string());
+                param.insert("token_id", token_id.to_string());
+                events.push(param);
+            }
+        }
+        CEP47Event::AdminBurn {
+            admin,
+            token_ids,
+            reason,
+        } => {
+            for token_id in token_ids {
+                let mut param = BTreeMap::new();
+                param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+                param.insert("event_type", "cep47_admin_burn_one".to_string());
+                param.insert("admin", admin.to_string());
+                param.insert("token_id", token_id.to_string());
+                param.insert("reason", reason.clone());
+                events.push(param);
+            }
+        }
+        CEP47Event::Approve {
+            owner,
+            spender,
+            token_ids,
+        } => {
+            for token_id in token_ids {
+                let mut param = BTreeMap::new();
+                param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+                param.insert("event_type", "cep47_approve_token".to_string());
+                param.insert("owner", owner.to_string());
+                param.insert("spender", spender.to_string());
+                param.insert("token_id", token_id.to_string());
+                events.push(param);
+            }
+        }
+        CEP47Event::ApprovalRevoked { owner, token_ids } => {
+            for token_id in token_ids {
+                let mut param = BTreeMap::new();
+                param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+                param.insert("event_type", "cep47_revoke_approval".to_string());
+                param.insert("owner", owner.to_string());
+                param.insert("token_id", token_id.to_string());
+                events.push(param);
+            }
+        }
+        CEP47Event::Transfer {
+            sender,
+            recipient,
+            token_ids,
+        } => {
+            for token_id in token_ids {
+                let mut param = BTreeMap::new();
+                param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+                param.insert("event_type", "cep47_transfer_token".to_string());
+                param.insert("sender", sender.to_string());
+                param.insert("recipient", recipient.to_string());
+                param.insert("token_id", token_id.to_string());
+                events.push(param);
+            }
+        }
+        CEP47Event::ApprovalForAll {
+            owner,
+            operator,
+            approved,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "cep47_approval_for_all".to_string());
+            param.insert("owner", owner.to_string());
+            param.insert("operator", operator.to_string());
+            param.insert("approved", approved.to_string());
+            events.push(param);
+        }
+        CEP47Event::MetadataUpdate { token_id } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "cep47_metadata_update".to_string());
+            param.insert("token_id", token_id.to_string());
+            events.push(param);
+        }
+        CEP47Event::MetadataFrozen { token_ids } => {
+            for token_id in token_ids {
+                let mut param = BTreeMap::new();
+                param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+                param.insert("event_type", "cep47_metadata_frozen".to_string());
+                param.insert("token_id", token_id.to_string());
+                events.push(param);
+            }
+        }
+        CEP47Event::BaseUriUpdate { base_uri } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "cep47_base_uri_update".to_string());
+            param.insert("base_uri", base_uri.clone());
+            events.push(param);
+        }
+        CEP47Event::BaseUriFrozen => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "cep47_base_uri_frozen".to_string());
+            events.push(param);
+        }
+        CEP47Event::CollectionMetadataFrozen => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "cep47_collection_metadata_frozen".to_string());
+            events.push(param);
+        }
+    };
+    for param in events {
+        let _: URef = storage::new_uref(param);
+    }
+}
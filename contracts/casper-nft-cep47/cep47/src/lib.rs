@@ -5,9 +5,13 @@ extern crate alloc;
 mod cep47;
 pub mod data;
 pub mod event;
+#[cfg(feature = "legacy-events")]
+mod legacy_event;
+pub mod meta_schema;
 
-pub use cep47::{Error, CEP47};
+pub use cep47::{Error, CEP47, CEP47_RECEIVED_ACK, ON_CEP47_RECEIVED};
 pub use contract_utils;
+pub use meta_schema::MetaSchema;
 
 use alloc::{collections::BTreeMap, string::String};
 use casper_types::U256;
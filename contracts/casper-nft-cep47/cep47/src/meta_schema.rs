@@ -0,0 +1,52 @@
+use alloc::{collections::BTreeMap, string::String};
+
+use crate::Meta;
+
+/// Required token metadata keys, each mapped to `(max_length, format)`.
+///
+/// A `max_length` of zero leaves the value length unbounded. `format` is one of
+/// [`FORMAT_ANY`], [`FORMAT_URL`] or [`FORMAT_NUMBER`].
+pub type MetaSchema = BTreeMap<String, (u32, String)>;
+
+pub const FORMAT_ANY: &str = "";
+pub const FORMAT_URL: &str = "url";
+pub const FORMAT_NUMBER: &str = "number";
+
+const URL_SCHEMES: [&str; 3] = ["https://", "http://", "ipfs://"];
+
+pub fn is_valid_schema(schema: &MetaSchema) -> bool {
+    schema.iter().all(|(key, (_, format))| {
+        !key.is_empty() && [FORMAT_ANY, FORMAT_URL, FORMAT_NUMBER].contains(&format.as_str())
+    })
+}
+
+/// Checks that `meta` carries every key of `schema` with a value of the expected length and
+/// format. Keys outside the schema are not restricted.
+pub fn validate(schema: &MetaSchema, meta: &Meta) -> bool {
+    schema
+        .iter()
+        .all(|(key, (max_length, format))| match meta.get(key) {
+            None => false,
+            Some(value) => {
+                (*max_length == 0 || value.len() <= *max_length as usize)
+                    && matches_format(value, format)
+            }
+        })
+}
+
+fn matches_format(value: &str, format: &str) -> bool {
+    match format {
+        FORMAT_URL => URL_SCHEMES.iter().any(|scheme| {
+            value.len() > scheme.len()
+                && value.starts_with(scheme)
+                && !value.chars().any(char::is_whitespace)
+        }),
+        FORMAT_NUMBER => {
+            let mut parts = value.splitn(2, '.');
+            let is_digits =
+                |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+            parts.next().map_or(false, is_digits) && parts.next().map_or(true, is_digits)
+        }
+        _ => true,
+    }
+}
@@ -0,0 +1,65 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{boxed::Box, format, string::String, vec};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    bytesrepr::Bytes, contracts::NamedKeys, CLType, CLTyped, CLValue, EntryPoint, EntryPointAccess,
+    EntryPointType, EntryPoints, Key, Parameter, U256,
+};
+
+const ACK: &str = "ack";
+
+/// Answers every safe transfer with the value passed as `ack` at install time.
+#[no_mangle]
+fn on_cep47_received() {
+    let ack_ref = runtime::get_key(ACK)
+        .unwrap_or_revert()
+        .into_uref()
+        .unwrap_or_revert();
+    let ack: String = storage::read(ack_ref).unwrap_or_revert().unwrap_or_revert();
+    runtime::ret(CLValue::from_t(ack).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn call() {
+    let contract_name: String = runtime::get_named_arg("contract_name");
+    let ack: String = runtime::get_named_arg(ACK);
+
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(String::from(ACK), storage::new_uref(ack).into());
+
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        "on_cep47_received",
+        vec![
+            Parameter::new("operator", Key::cl_type()),
+            Parameter::new("from", Key::cl_type()),
+            Parameter::new("token_ids", CLType::List(Box::new(U256::cl_type()))),
+            Parameter::new("data", Bytes::cl_type()),
+        ],
+        String::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    let (contract_hash, _) = storage::new_contract(
+        entry_points,
+        Some(named_keys),
+        Some(format!("{}_package_hash", contract_name)),
+        None,
+    );
+    runtime::put_key(
+        &format!("{}_contract_hash", contract_name),
+        contract_hash.into(),
+    );
+    runtime::put_key(
+        &format!("{}_contract_hash_wrapped", contract_name),
+        storage::new_uref(contract_hash).into(),
+    );
+}
@@ -4,11 +4,15 @@
 extern crate alloc;
 
 mod admin_control;
+mod ces;
 mod contract_context;
 mod contract_storage;
 mod data;
+mod role_control;
 
 pub use admin_control::AdminControl;
+pub use ces::{CesEvents, Schema, Schemas};
 pub use contract_context::ContractContext;
 pub use contract_storage::{ContractStorage, OnChainContractStorage};
 pub use data::{get_key, key_and_value_to_str, key_to_str, set_key, Dict};
+pub use role_control::RoleControl;
@@ -0,0 +1,70 @@
+use alloc::string::String;
+use casper_contract::contract_api::runtime;
+use casper_types::{ApiError, Key};
+
+use crate::{key_and_value_to_str, AdminControl, ContractStorage, Dict};
+
+const ROLES_DICT: &str = "roles";
+
+pub trait RoleControl<Storage: ContractStorage>: AdminControl<Storage> {
+    fn init(&mut self) {
+        Roles::init();
+    }
+
+    fn grant_role(&mut self, role: &str, address: Key) {
+        self.assert_caller_is_admin();
+        self.grant_role_without_checked(role, address);
+    }
+
+    fn revoke_role(&mut self, role: &str, address: Key) {
+        self.assert_caller_is_admin();
+        Roles::instance().revoke_role(role, &address);
+    }
+
+    fn grant_role_without_checked(&mut self, role: &str, address: Key) {
+        Roles::instance().grant_role(role, &address);
+    }
+
+    fn assert_caller_has_role(&self, role: &str) {
+        let caller = self.get_caller();
+        if !self.has_role(role, caller) {
+            runtime::revert(ApiError::User(21));
+        }
+    }
+
+    fn has_role(&self, role: &str, address: Key) -> bool {
+        Roles::instance().has_role(role, &address)
+    }
+}
+
+struct Roles {
+    dict: Dict,
+}
+
+impl Roles {
+    pub fn instance() -> Roles {
+        Roles {
+            dict: Dict::instance(ROLES_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(ROLES_DICT);
+    }
+
+    pub fn has_role(&self, role: &str, key: &Key) -> bool {
+        self.dict.get::<()>(&role_key(role, key)).is_some()
+    }
+
+    pub fn grant_role(&self, role: &str, key: &Key) {
+        self.dict.set(&role_key(role, key), ());
+    }
+
+    pub fn revoke_role(&self, role: &str, key: &Key) {
+        self.dict.remove::<()>(&role_key(role, key));
+    }
+}
+
+fn role_key(role: &str, key: &Key) -> String {
+    key_and_value_to_str(key, &String::from(role))
+}
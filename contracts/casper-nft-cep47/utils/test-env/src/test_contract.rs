@@ -31,6 +31,13 @@ impl TestContract {
         }
     }
 
+    pub fn upgrade(&self, sender: AccountHash, wasm: &str) {
+        let session_code = PathBuf::from(wasm);
+        let mut args = RuntimeArgs::new();
+        args.insert("contract_name", self.name.clone()).unwrap();
+        self.env.run(sender, DeploySource::Code(session_code), args);
+    }
+
     pub fn query_dictionary<T: CLTyped + FromBytes>(
         &self,
         dict_name: &str,
@@ -40,6 +47,15 @@ impl TestContract {
             .query_dictionary(self.contract_hash(), dict_name, key)
     }
 
+    pub fn query_dictionary_value<T: CLTyped + FromBytes>(
+        &self,
+        dict_name: &str,
+        key: String,
+    ) -> Option<T> {
+        self.env
+            .query_dictionary_value(self.contract_hash(), dict_name, key)
+    }
+
     pub fn query_named_key<T: CLTyped + FromBytes>(&self, key: String) -> T {
         let contract_name = format!("{}_contract_hash", self.name);
         self.env
@@ -52,6 +68,14 @@ impl TestContract {
             .query_account_named_key(self.contract_owner, &[key])
     }
 
+    pub fn package_hash(&self) -> [u8; 32] {
+        let key = format!("{}_package_hash", self.name);
+        self.env
+            .account_named_key(self.contract_owner, &key)
+            .into_hash()
+            .unwrap()
+    }
+
     pub fn call_contract(&self, sender: AccountHash, entry_point: &str, session_args: RuntimeArgs) {
         let session_code = DeploySource::ByHash {
             hash: ContractHash::new(self.contract_hash()),
@@ -46,6 +46,19 @@ impl TestEnv {
             .query_dictionary(contract_hash, dict_name.to_string(), key)
     }
 
+    /// Reads a dictionary value written bare rather than wrapped in an `Option`.
+    pub fn query_dictionary_value<T: CLTyped + FromBytes>(
+        &self,
+        contract_hash: [u8; 32],
+        dict_name: &str,
+        key: String,
+    ) -> Option<T> {
+        self.state
+            .lock()
+            .unwrap()
+            .query_dictionary_value(contract_hash, dict_name.to_string(), key)
+    }
+
     pub fn query_account_named_key<T: CLTyped + FromBytes>(
         &self,
         account: AccountHash,
@@ -56,6 +69,11 @@ impl TestEnv {
             .unwrap()
             .query_account_named_key(account, path)
     }
+
+    /// The key stored under `name` in the account's named keys, e.g. a package hash.
+    pub fn account_named_key(&self, account: AccountHash, name: &str) -> Key {
+        self.state.lock().unwrap().account_named_key(account, name)
+    }
 }
 
 impl Default for TestEnv {
@@ -152,6 +170,33 @@ impl TestEnvState {
         }
     }
 
+    pub fn query_dictionary_value<T: CLTyped + FromBytes>(
+        &self,
+        contract_hash: [u8; 32],
+        dict_name: String,
+        dictionary_item_key: String,
+    ) -> Option<T> {
+        match query_dictionary_item(
+            &self.builder,
+            Key::Hash(contract_hash),
+            Some(dict_name),
+            dictionary_item_key,
+        ) {
+            Ok(value) => Some(
+                value
+                    .as_cl_value()
+                    .expect("should be cl value.")
+                    .clone()
+                    .into_t()
+                    .expect("Wrong type in query result."),
+            ),
+            Err(e) => {
+                println!("{}", e);
+                None
+            }
+        }
+    }
+
     pub fn query_account_named_key<T: CLTyped + FromBytes>(
         &self,
         account: AccountHash,
@@ -159,4 +204,14 @@ impl TestEnvState {
     ) -> T {
         query(&self.builder, Key::Account(account), path)
     }
+
+    pub fn account_named_key(&self, account: AccountHash, name: &str) -> Key {
+        *self
+            .builder
+            .get_account(account)
+            .expect("should have account")
+            .named_keys()
+            .get(name)
+            .expect("should have named key")
+    }
 }
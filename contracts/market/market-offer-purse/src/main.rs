@@ -18,7 +18,8 @@ pub extern "C" fn call() {
     let amount: U512 = runtime::get_named_arg("amount");
     let purse = create_purse();
     transfer_from_purse_to_purse(get_main_purse(), purse, amount, None).unwrap_or_revert();
-    runtime::call_contract(
+    // The sale receipt: `((item_id, seller, buyer), (price, fee, royalty))`.
+    let _: ((U256, Key, Key), (U512, U512, U512)) = runtime::call_contract(
         runtime::get_named_arg("market_contract_hash"),
         "process_market_sale",
         runtime_args! {
@@ -27,5 +28,5 @@ pub extern "C" fn call() {
             "amount" => amount,
             "market_offer_purse" => purse,
         },
-    )
+    );
 }
@@ -0,0 +1,44 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    bytesrepr::{self, Bytes, FromBytes},
+    CLType, CLTyped, ContractHash, RuntimeArgs,
+};
+
+/// Name of the account key the return value is stored under.
+const RESULT_KEY: &str = "query_result";
+
+/// An entry point's return value, kept in its bytesrepr encoding whatever its type.
+struct RawValue(Vec<u8>);
+
+impl CLTyped for RawValue {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl FromBytes for RawValue {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        Ok((RawValue(bytes.to_vec()), &[]))
+    }
+}
+
+/// Calls `entry_point` of the contract with `args`, the bytesrepr encoded `RuntimeArgs`, and
+/// stores what it returns as `Bytes` under the caller's `query_result` key.
+#[no_mangle]
+pub extern "C" fn call() {
+    let contract_hash: ContractHash = runtime::get_named_arg("contract_hash");
+    let entry_point: String = runtime::get_named_arg("entry_point");
+    let args: Bytes = runtime::get_named_arg("args");
+    let (args, _) = RuntimeArgs::from_bytes(&args).unwrap_or_revert();
+    let RawValue(result) = runtime::call_contract(contract_hash, &entry_point, args);
+    runtime::put_key(RESULT_KEY, storage::new_uref(Bytes::from(result)).into());
+}
@@ -11,30 +11,38 @@ use casper_contract::{
     unwrap_or_revert::UnwrapOrRevert,
 };
 use casper_types::{
-    CLType, CLTyped, CLValue, ContractHash, ContractPackageHash, EntryPoint, EntryPointAccess,
-    EntryPoints, EntryPointType, Group, Key, Parameter, runtime_args, RuntimeArgs, U256, U512,
-    URef,
+    contracts::NamedKeys, CLType, CLTyped, CLValue, ContractHash, ContractPackageHash, EntryPoint,
+    EntryPointAccess, EntryPoints, EntryPointType, Group, Key, Parameter, runtime_args,
+    RuntimeArgs, U256, U512, URef,
 };
 use casper_types::account::AccountHash;
-use casper_types::bytesrepr::ToBytes;
-use contract_utils::{ContractContext, OnChainContractStorage};
-use market::{Error, MarketContract, Meta, NFTContractAddress, MarketItemId, TokenId};
+use casper_types::bytesrepr::{Bytes, ToBytes};
+use contract_utils::{AdminControl, ContractContext, OnChainContractStorage};
+use market::{
+    CollectionStats, Error, MarketContract, MarketInfo, MarketItem, MarketItemId,
+    MarketItemStatus, Meta, NFTContractAddress, SaleReceipt, SaleRecord, TokenId,
+};
 use market::data::{MARKET_NAME, META, SYMBOL};
 
 #[derive(Default)]
-struct MarketItem(OnChainContractStorage);
+struct Market(OnChainContractStorage);
 
-impl ContractContext<OnChainContractStorage> for MarketItem {
+impl ContractContext<OnChainContractStorage> for Market {
     fn storage(&self) -> &OnChainContractStorage {
         &self.0
     }
 }
 
-impl MarketContract<OnChainContractStorage> for MarketItem {}
+impl MarketContract<OnChainContractStorage> for Market {}
+
+impl AdminControl<OnChainContractStorage> for Market {}
 
-impl MarketItem {
+impl Market {
     fn constructor(&mut self, name: String, symbol: String, meta: Meta) {
         MarketContract::init(self, name, symbol, meta);
+        AdminControl::init(self);
+        let installer = self.get_caller();
+        self.add_admin_without_checked(installer);
     }
 }
 
@@ -44,43 +52,63 @@ fn constructor() {
     let name = runtime::get_named_arg::<String>(MARKET_NAME);
     let symbol = runtime::get_named_arg::<String>(SYMBOL);
     let meta = runtime::get_named_arg::<Meta>(META);
-    MarketItem::default().constructor(name, symbol, meta);
+    Market::default().constructor(name, symbol, meta);
 }
 
 #[no_mangle]
 fn name() {
-    let ret = MarketItem::default().name();
+    let ret = Market::default().name();
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
+/// Deprecated alias of `name`, kept for callers of earlier versions. The market's package hash,
+/// which CEP-47 approvals name as spender, is in the `market_item_hash` named key instead.
 #[no_mangle]
 fn market_item_hash() {
-    let ret = MarketItem::default().name();
+    let ret = Market::default().name();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn on_cep47_received() {
+    let operator = runtime::get_named_arg::<Key>("operator");
+    let from = runtime::get_named_arg::<Key>("from");
+    let token_ids = runtime::get_named_arg::<Vec<TokenId>>("token_ids");
+    let data = runtime::get_named_arg::<Bytes>("data");
+    let ret = Market::default()
+        .on_cep47_received(operator, from, token_ids, data)
+        .unwrap_or_revert();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn market_info() {
+    let ret = Market::default().market_info();
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
 #[no_mangle]
 fn symbol() {
-    let ret = MarketItem::default().symbol();
+    let ret = Market::default().symbol();
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
 #[no_mangle]
 fn meta() {
-    let ret = MarketItem::default().meta();
+    let ret = Market::default().meta();
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
 #[no_mangle]
 fn total_supply() {
-    let ret = MarketItem::default().total_supply();
+    let ret = Market::default().total_supply();
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
 #[no_mangle]
 fn balance_of() {
     let owner = runtime::get_named_arg::<Key>("owner");
-    let ret = MarketItem::default().balance_of(owner);
+    let ret = Market::default().balance_of(owner);
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
@@ -88,21 +116,28 @@ fn balance_of() {
 fn get_item_by_index() {
     let owner = runtime::get_named_arg::<Key>("owner");
     let index = runtime::get_named_arg::<U256>("index");
-    let ret = MarketItem::default().get_item_by_index(owner, index);
+    let ret = Market::default().get_item_by_index(owner, index);
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
 #[no_mangle]
 fn owner_of_market_item() {
     let item_id = runtime::get_named_arg::<MarketItemId>("item_id");
-    let ret = MarketItem::default().owner_of(item_id);
+    let ret = Market::default().owner_of(item_id);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn get_market_item() {
+    let item_id = runtime::get_named_arg::<MarketItemId>("item_id");
+    let ret = Market::default().listing(item_id);
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
 #[no_mangle]
 fn token_market_status() {
     let item_token_id = runtime::get_named_arg::<MarketItemId>("item_token_id");
-    let ret = MarketItem::default().token_market_status(item_token_id);
+    let ret = Market::default().token_market_status(item_token_id);
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
@@ -110,41 +145,59 @@ fn token_market_status() {
 #[no_mangle]
 fn item_nft_contract_address() {
     let item_id = runtime::get_named_arg::<MarketItemId>("item_id");
-    let ret = MarketItem::default().item_nft_contract_address(item_id);
+    let ret = Market::default().item_nft_contract_address(item_id);
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
 #[no_mangle]
 fn item_asking_price() {
     let item_id = runtime::get_named_arg::<MarketItemId>("item_id");
-    let ret = MarketItem::default().item_asking_price(item_id);
+    let ret = Market::default().item_asking_price(item_id);
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
 #[no_mangle]
 fn item_token_id() {
     let item_id = runtime::get_named_arg::<MarketItemId>("item_id");
-    let ret = MarketItem::default().item_token_id(item_id);
+    let ret = Market::default().item_token_id(item_id);
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
 #[no_mangle]
 fn item_status() {
     let item_id = runtime::get_named_arg::<MarketItemId>("item_id");
-    let ret = MarketItem::default().item_status(item_id);
+    let ret = Market::default().item_status(item_id);
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
 #[no_mangle]
 fn create_market_item() {
     let recipient = runtime::get_named_arg::<Key>("recipient");
-    let item_ids = runtime::get_named_arg::<Vec<MarketItemId>>("item_ids");
     let item_nft_contract_addresses =
         runtime::get_named_arg::<Vec<NFTContractAddress>>("item_nft_contract_addresses");
     let item_asking_prices = runtime::get_named_arg::<Vec<U512>>("item_asking_prices");
     let item_token_ids = runtime::get_named_arg::<Vec<U256>>("item_token_ids");
-    MarketItem::default()
+    let ret = Market::default()
         .create_market_item(
+            recipient,
+            item_nft_contract_addresses,
+            item_asking_prices,
+            item_token_ids,
+        )
+        .unwrap_or_revert();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn create_market_item_with_ids() {
+    let recipient = runtime::get_named_arg::<Key>("recipient");
+    let item_ids = runtime::get_named_arg::<Vec<MarketItemId>>("item_ids");
+    let item_nft_contract_addresses =
+        runtime::get_named_arg::<Vec<NFTContractAddress>>("item_nft_contract_addresses");
+    let item_asking_prices = runtime::get_named_arg::<Vec<U512>>("item_asking_prices");
+    let item_token_ids = runtime::get_named_arg::<Vec<U256>>("item_token_ids");
+    let ret = Market::default()
+        .create_market_item_with_ids(
             recipient,
             item_ids,
             item_nft_contract_addresses,
@@ -152,6 +205,7 @@ fn create_market_item() {
             item_token_ids,
         )
         .unwrap_or_revert();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
 #[no_mangle]
@@ -159,18 +213,180 @@ fn process_market_sale() {
     let recipient = runtime::get_named_arg::<Key>("recipient");
     let item_id = runtime::get_named_arg::<MarketItemId>("item_id");
     let market_offer_purse = runtime::get_named_arg::<URef>("market_offer_purse");
-    MarketItem::default()
+    let ret = Market::default()
         .process_market_sale(recipient, item_id, market_offer_purse)
         .unwrap_or_revert();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn seller_history_count() {
+    let owner = runtime::get_named_arg::<Key>("owner");
+    let ret = Market::default().seller_history_count(owner);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn seller_history() {
+    let owner = runtime::get_named_arg::<Key>("owner");
+    let offset = runtime::get_named_arg::<u64>("offset");
+    let limit = runtime::get_named_arg::<u64>("limit");
+    let ret = Market::default().seller_history(owner, offset, limit);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn collection_stats() {
+    let nft_contract_address =
+        runtime::get_named_arg::<NFTContractAddress>("nft_contract_address");
+    let ret = Market::default().collection_stats(nft_contract_address);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn last_sale_price() {
+    let nft_contract_address =
+        runtime::get_named_arg::<NFTContractAddress>("nft_contract_address");
+    let token_id = runtime::get_named_arg::<TokenId>("token_id");
+    let ret = Market::default().last_sale_price(nft_contract_address, token_id);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn sale_history() {
+    let nft_contract_address =
+        runtime::get_named_arg::<NFTContractAddress>("nft_contract_address");
+    let token_id = runtime::get_named_arg::<TokenId>("token_id");
+    let offset = runtime::get_named_arg::<u64>("offset");
+    let limit = runtime::get_named_arg::<u64>("limit");
+    let ret = Market::default().sale_history(nft_contract_address, token_id, offset, limit);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn active_listings_count() {
+    let ret = Market::default().active_listings_count();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn active_listing_by_index() {
+    let index = runtime::get_named_arg::<U256>("index");
+    let ret = Market::default().active_listing_by_index(index);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn active_listings() {
+    let offset = runtime::get_named_arg::<u64>("offset");
+    let limit = runtime::get_named_arg::<u64>("limit");
+    let ret = Market::default().active_listings(offset, limit);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn listings_by_seller() {
+    let owner = runtime::get_named_arg::<Key>("owner");
+    let offset = runtime::get_named_arg::<u64>("offset");
+    let limit = runtime::get_named_arg::<u64>("limit");
+    let ret = Market::default().listings_by_seller(owner, offset, limit);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn listings_by_collection() {
+    let nft_contract_address =
+        runtime::get_named_arg::<NFTContractAddress>("nft_contract_address");
+    let offset = runtime::get_named_arg::<u64>("offset");
+    let limit = runtime::get_named_arg::<u64>("limit");
+    let ret = Market::default().listings_by_collection(nft_contract_address, offset, limit);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn cancel_market_item() {
+    let item_id = runtime::get_named_arg::<MarketItemId>("item_id");
+    Market::default()
+        .cancel_market_item(item_id)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+fn invalidate_market_item() {
+    let item_id = runtime::get_named_arg::<MarketItemId>("item_id");
+    Market::default()
+        .invalidate_market_item(item_id)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+fn set_item_status() {
+    let item_id = runtime::get_named_arg::<MarketItemId>("item_id");
+    let status = runtime::get_named_arg::<MarketItemStatus>("status");
+    let mut market = Market::default();
+    market.assert_caller_is_admin();
+    market.set_item_status(item_id, status).unwrap_or_revert();
+}
+
+#[no_mangle]
+fn migrate() {
+    let mut market = Market::default();
+    market.assert_caller_is_admin();
+    market.migrate().unwrap_or_revert();
+}
+
+#[no_mangle]
+fn schema_version() {
+    let ret = Market::default().schema_version();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn collection_capabilities() {
+    let nft_contract_address =
+        runtime::get_named_arg::<NFTContractAddress>("nft_contract_address");
+    let ret = Market::default().collection_capabilities(nft_contract_address);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn set_collection_capabilities() {
+    let nft_contract_address =
+        runtime::get_named_arg::<NFTContractAddress>("nft_contract_address");
+    let capabilities = runtime::get_named_arg::<u8>("capabilities");
+    let mut market = Market::default();
+    market.assert_caller_is_admin();
+    market
+        .set_collection_capabilities(nft_contract_address, capabilities)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+fn set_fee_config() {
+    let fee_basis_points = runtime::get_named_arg::<u32>("fee_basis_points");
+    let fee_recipient = runtime::get_named_arg::<Key>("fee_recipient");
+    let mut market = Market::default();
+    market.assert_caller_is_admin();
+    market
+        .set_fee_config(fee_basis_points, fee_recipient)
+        .unwrap_or_revert();
 }
 
 #[no_mangle]
 fn call() {
+    let contract_name: String = runtime::get_named_arg("contract_name");
+    // Running the session again against an existing package adds a new version to it.
+    match runtime::get_key(&format!("{}_package_hash", contract_name)) {
+        Some(package_key) => upgrade(contract_name, package_key),
+        None => install(contract_name),
+    }
+}
+
+fn install(contract_name: String) {
     // Read arguments for the constructor call.
     let name: String = runtime::get_named_arg(MARKET_NAME);
     let symbol: String = runtime::get_named_arg(SYMBOL);
     let meta: Meta = runtime::get_named_arg(META);
-    let contract_name: String = runtime::get_named_arg("contract_name");
 
     // Prepare constructor args
     let constructor_args = runtime_args! {
@@ -182,12 +398,12 @@ fn call() {
     let (contract_hash, _) = storage::new_contract(
         get_entry_points(),
         None,
-        Some(String::from("contract_package_hash")),
-        None,
+        Some(format!("{}_package_hash", contract_name)),
+        Some(format!("{}_access_uref", contract_name)),
     );
 
     let package_hash: ContractPackageHash = ContractPackageHash::new(
-        runtime::get_key("contract_package_hash")
+        runtime::get_key(&format!("{}_package_hash", contract_name))
             .unwrap_or_revert()
             .into_hash()
             .unwrap_or_revert(),
@@ -206,6 +422,29 @@ fn call() {
     storage::remove_contract_user_group_urefs(package_hash, "constructor", urefs)
         .unwrap_or_revert();
 
+    put_contract_hash_keys(&contract_name, contract_hash);
+}
+
+fn upgrade(contract_name: String, package_key: Key) {
+    let package_hash = ContractPackageHash::new(package_key.into_hash().unwrap_or_revert());
+    let previous_contract_hash = ContractHash::new(
+        runtime::get_key(&format!("{}_contract_hash", contract_name))
+            .unwrap_or_revert()
+            .into_hash()
+            .unwrap_or_revert(),
+    );
+
+    // The new version inherits the previous version's named keys, dictionaries included.
+    let (contract_hash, _) =
+        storage::add_contract_version(package_hash, get_entry_points(), NamedKeys::new());
+    storage::disable_contract_version(package_hash, previous_contract_hash).unwrap_or_revert();
+
+    let _: () = runtime::call_contract(contract_hash, "migrate", runtime_args! {});
+
+    put_contract_hash_keys(&contract_name, contract_hash);
+}
+
+fn put_contract_hash_keys(contract_name: &str, contract_hash: ContractHash) {
     runtime::put_key(
         &format!("{}_contract_hash", contract_name),
         contract_hash.into(),
@@ -236,6 +475,32 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "market_item_hash",
+        vec![],
+        String::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "on_cep47_received",
+        vec![
+            Parameter::new("operator", Key::cl_type()),
+            Parameter::new("from", Key::cl_type()),
+            Parameter::new("token_ids", CLType::List(Box::new(TokenId::cl_type()))),
+            Parameter::new("data", Bytes::cl_type()),
+        ],
+        String::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "market_info",
+        vec![],
+        MarketInfo::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "symbol",
         vec![],
@@ -274,33 +539,65 @@ fn get_entry_points() -> EntryPoints {
     entry_points.add_entry_point(EntryPoint::new(
         "item_nft_contract_address",
         vec![Parameter::new("item_id", MarketItemId::cl_type())],
-        NFTContractAddress::cl_type(),
+        CLType::Option(Box::new(NFTContractAddress::cl_type())),
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
     entry_points.add_entry_point(EntryPoint::new(
         "item_asking_price",
         vec![Parameter::new("item_id", MarketItemId::cl_type())],
-        U512::cl_type(),
+        CLType::Option(Box::new(U512::cl_type())),
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
     entry_points.add_entry_point(EntryPoint::new(
         "item_token_id",
         vec![Parameter::new("item_id", MarketItemId::cl_type())],
-        U256::cl_type(),
+        CLType::Option(Box::new(TokenId::cl_type())),
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
     entry_points.add_entry_point(EntryPoint::new(
         "token_market_status",
         vec![Parameter::new("item_token_id", TokenId::cl_type())],
-        String::cl_type(),
+        CLType::Option(Box::new(MarketItemStatus::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "item_status",
+        vec![Parameter::new("item_id", MarketItemId::cl_type())],
+        CLType::Option(Box::new(MarketItemStatus::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_market_item",
+        vec![Parameter::new("item_id", MarketItemId::cl_type())],
+        CLType::Option(Box::new(MarketItem::cl_type())),
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
     entry_points.add_entry_point(EntryPoint::new(
         "create_market_item",
+        vec![
+            Parameter::new("recipient", Key::cl_type()),
+            Parameter::new(
+                "item_nft_contract_addresses",
+                CLType::List(Box::new(NFTContractAddress::cl_type())),
+            ),
+            Parameter::new(
+                "item_asking_prices",
+                CLType::List(Box::new(U512::cl_type())),
+            ),
+            Parameter::new("item_token_ids", CLType::List(Box::new(U256::cl_type()))),
+        ],
+        CLType::List(Box::new(MarketItemId::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "create_market_item_with_ids",
         vec![
             Parameter::new("recipient", Key::cl_type()),
             Parameter::new("item_ids", CLType::List(Box::new(MarketItemId::cl_type()))),
@@ -310,11 +607,11 @@ fn get_entry_points() -> EntryPoints {
             ),
             Parameter::new(
                 "item_asking_prices",
-                CLType::List(Box::new(U256::cl_type())),
+                CLType::List(Box::new(U512::cl_type())),
             ),
             Parameter::new("item_token_ids", CLType::List(Box::new(U256::cl_type()))),
         ],
-        <()>::cl_type(),
+        CLType::List(Box::new(MarketItemId::cl_type())),
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
@@ -325,10 +622,171 @@ fn get_entry_points() -> EntryPoints {
             Parameter::new("item_id", MarketItemId::cl_type()),
             Parameter::new("market_offer_purse", URef::cl_type()),
         ],
+        SaleReceipt::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "seller_history_count",
+        vec![Parameter::new("owner", Key::cl_type())],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "seller_history",
+        vec![
+            Parameter::new("owner", Key::cl_type()),
+            Parameter::new("offset", u64::cl_type()),
+            Parameter::new("limit", u64::cl_type()),
+        ],
+        CLType::List(Box::new(MarketItem::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "collection_stats",
+        vec![Parameter::new("nft_contract_address", NFTContractAddress::cl_type())],
+        CollectionStats::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "last_sale_price",
+        vec![
+            Parameter::new("nft_contract_address", NFTContractAddress::cl_type()),
+            Parameter::new("token_id", TokenId::cl_type()),
+        ],
+        CLType::Option(Box::new(U512::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "sale_history",
+        vec![
+            Parameter::new("nft_contract_address", NFTContractAddress::cl_type()),
+            Parameter::new("token_id", TokenId::cl_type()),
+            Parameter::new("offset", u64::cl_type()),
+            Parameter::new("limit", u64::cl_type()),
+        ],
+        CLType::List(Box::new(SaleRecord::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "active_listings_count",
+        vec![],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "active_listing_by_index",
+        vec![Parameter::new("index", U256::cl_type())],
+        CLType::Option(Box::new(MarketItemId::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "active_listings",
+        vec![
+            Parameter::new("offset", u64::cl_type()),
+            Parameter::new("limit", u64::cl_type()),
+        ],
+        CLType::List(Box::new(MarketItem::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "listings_by_seller",
+        vec![
+            Parameter::new("owner", Key::cl_type()),
+            Parameter::new("offset", u64::cl_type()),
+            Parameter::new("limit", u64::cl_type()),
+        ],
+        CLType::List(Box::new(MarketItem::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "listings_by_collection",
+        vec![
+            Parameter::new("nft_contract_address", NFTContractAddress::cl_type()),
+            Parameter::new("offset", u64::cl_type()),
+            Parameter::new("limit", u64::cl_type()),
+        ],
+        CLType::List(Box::new(MarketItem::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "cancel_market_item",
+        vec![Parameter::new("item_id", MarketItemId::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "invalidate_market_item",
+        vec![Parameter::new("item_id", MarketItemId::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_item_status",
+        vec![
+            Parameter::new("item_id", MarketItemId::cl_type()),
+            Parameter::new("status", MarketItemStatus::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "migrate",
+        vec![],
         <()>::cl_type(),
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "collection_capabilities",
+        vec![Parameter::new(
+            "nft_contract_address",
+            NFTContractAddress::cl_type(),
+        )],
+        u8::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_collection_capabilities",
+        vec![
+            Parameter::new("nft_contract_address", NFTContractAddress::cl_type()),
+            Parameter::new("capabilities", u8::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_fee_config",
+        vec![
+            Parameter::new("fee_basis_points", u32::cl_type()),
+            Parameter::new("fee_recipient", Key::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "schema_version",
+        vec![],
+        u32::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "get_item_by_index",
         vec![
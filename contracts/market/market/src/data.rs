@@ -2,86 +2,73 @@ use alloc::{format, vec};
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use casper_contract::{contract_api::runtime::get_call_stack, unwrap_or_revert::UnwrapOrRevert};
-use casper_types::{ContractPackageHash, Key, system::CallStackElement, U256, U512, URef};
-use contract_utils::{Dict, get_key, key_and_value_to_str, key_to_str, set_key};
+use casper_types::{ContractHash, ContractPackageHash, Key, system::CallStackElement, U256, U512};
+use contract_utils::{CesEvents, Dict, get_key, key_and_value_to_str, key_to_str, set_key};
 
-use crate::{event::MarketEvent, Meta, NFTContractAddress, MarketItemId, TokenId};
+use crate::{
+    event::MarketEvent, CollectionStats, MarketItem, MarketItemId, Meta, NFTContractAddress,
+    SaleRecord, TokenId,
+};
 
 const BALANCES_DICT: &str = "item_balances";
 pub const ALLOWANCES_DICT: &str = "item_allowances";
-const NFT_CONTRACT_ADDRESSES: &str = "nft_contract_addresses";
+const MARKET_ITEMS_DICT: &str = "market_items";
 const NFT_MARKET_ITEM_IDS: &str = "nft_market_item_ids";
-const ITEM_ASKING_PRICE_DATA: &str = "item_asking_prices";
-const ITEM_TOKEN_ID_DATA: &str = "item_token_ids";
-const ITEM_STATUS_DATA: &str = "item_statuses";
-const ITEM_PURSE_DATA: &str = "item_purses";
-const OWNERS_DICT: &str = "item_owners";
 
 const OWNED_ITEMS_BY_INDEX_DICT: &str = "owned_items_by_index";
 const OWNED_INDEXES_BY_ITEM_DICT: &str = "owned_indexes_by_item";
+const ACTIVE_ITEMS_BY_INDEX_DICT: &str = "active_items_by_index";
+const ACTIVE_INDEXES_BY_ITEM_DICT: &str = "active_indexes_by_item";
+const COLLECTION_ITEMS_BY_INDEX_DICT: &str = "collection_items_by_index";
+const COLLECTION_INDEXES_BY_ITEM_DICT: &str = "collection_indexes_by_item";
+const COLLECTION_ITEM_COUNTS_DICT: &str = "collection_item_counts";
+const SELLER_HISTORY_BY_INDEX_DICT: &str = "seller_history_by_index";
+const SELLER_HISTORY_COUNTS_DICT: &str = "seller_history_counts";
+const TOKEN_SALES_BY_INDEX_DICT: &str = "token_sales_by_index";
+const TOKEN_SALE_COUNTS_DICT: &str = "token_sale_counts";
+const COLLECTION_STATS_DICT: &str = "collection_stats";
+const FLOOR_HEAP_ENTRIES_DICT: &str = "floor_heap_entries";
+const FLOOR_HEAP_POSITIONS_DICT: &str = "floor_heap_positions";
+const FLOOR_HEAP_SIZES_DICT: &str = "floor_heap_sizes";
+const CUSTODY_ITEMS_DICT: &str = "custody_items";
+const COLLECTION_CAPABILITIES_DICT: &str = "collection_capabilities";
+pub const ACTIVE_ITEMS_COUNT: &str = "active_items_count";
 pub const NFT_CONTRACT_ADDRESS: &str = "nft_contract_hash";
 pub const TOTAL_SUPPLY: &str = "item_total_supply";
+pub const NEXT_ITEM_ID: &str = "next_item_id";
+pub const FEE_BASIS_POINTS: &str = "fee_basis_points";
+pub const FEE_RECIPIENT: &str = "fee_recipient";
+pub const SCHEMA_VERSION: &str = "schema_version";
+/// Storage layout version written by this build of the contract.
+pub const CURRENT_SCHEMA_VERSION: u32 = 1;
 pub const MARKET_NAME: &str = "market_name";
 pub const SYMBOL: &str = "market_symbol";
 pub const META: &str = "market_meta";
 
-pub struct Owners {
+pub struct MarketItems {
     dict: Dict,
 }
 
-impl Owners {
-    pub fn instance() -> Owners {
-        Owners {
-            dict: Dict::instance(OWNERS_DICT),
+impl MarketItems {
+    pub fn instance() -> MarketItems {
+        MarketItems {
+            dict: Dict::instance(MARKET_ITEMS_DICT),
         }
     }
 
     pub fn init() {
-        Dict::init(OWNERS_DICT)
+        Dict::init(MARKET_ITEMS_DICT)
     }
 
-    pub fn get(&self, key: &MarketItemId) -> Option<Key> {
+    pub fn get(&self, key: &MarketItemId) -> Option<MarketItem> {
         self.dict.get(&key.to_string())
     }
 
-    pub fn set(&self, key: &MarketItemId, value: Key) {
+    pub fn set(&self, key: &MarketItemId, value: MarketItem) {
         self.dict.set(&key.to_string(), value);
     }
-
-    pub fn remove(&self, key: &MarketItemId) {
-        self.dict.remove::<Key>(&key.to_string());
-    }
-}
-
-pub struct NFTContractAddresses {
-    dict: Dict,
 }
 
-impl NFTContractAddresses {
-    pub fn instance() -> NFTContractAddresses {
-        NFTContractAddresses {
-            dict: Dict::instance(NFT_CONTRACT_ADDRESSES),
-        }
-    }
-
-    pub fn init() {
-        Dict::init(NFT_CONTRACT_ADDRESSES)
-    }
-
-    pub fn get(&self, key: &MarketItemId) -> Option<NFTContractAddress> {
-        self.dict.get(&key.to_string())
-    }
-
-    pub fn set(&self, key: &MarketItemId, value: NFTContractAddress) {
-        self.dict.set(&key.to_string(), value);
-    }
-
-    pub fn remove(&self, key: &MarketItemId) {
-        self.dict.remove::<NFTContractAddress>(&key.to_string());
-    }
-}
-
-
 pub struct NFTMarketItemIds {
     dict: Dict,
 }
@@ -122,90 +109,6 @@ impl NFTMarketItemIds {
 }
 
 
-pub struct ItemAskingPriceData {
-    dict: Dict,
-}
-
-impl ItemAskingPriceData {
-    pub fn instance() -> ItemAskingPriceData {
-        ItemAskingPriceData {
-            dict: Dict::instance(ITEM_ASKING_PRICE_DATA),
-        }
-    }
-
-    pub fn init() {
-        Dict::init(ITEM_ASKING_PRICE_DATA)
-    }
-
-    pub fn get(&self, key: &MarketItemId) -> Option<U512> {
-        self.dict.get(&key.to_string())
-    }
-
-    pub fn set(&self, key: &MarketItemId, value: U512) {
-        self.dict.set(&key.to_string(), value);
-    }
-
-    pub fn remove(&self, key: &MarketItemId) {
-        self.dict.remove::<U512>(&key.to_string());
-    }
-}
-
-pub struct ItemTokenIdData {
-    dict: Dict,
-}
-
-impl ItemTokenIdData {
-    pub fn instance() -> ItemTokenIdData {
-        ItemTokenIdData {
-            dict: Dict::instance(ITEM_TOKEN_ID_DATA),
-        }
-    }
-
-    pub fn init() {
-        Dict::init(ITEM_TOKEN_ID_DATA)
-    }
-
-    pub fn get(&self, key: &MarketItemId) -> Option<U256> {
-        self.dict.get(&key.to_string())
-    }
-
-    pub fn set(&self, key: &MarketItemId, value: U256) {
-        self.dict.set(&key.to_string(), value);
-    }
-
-    pub fn remove(&self, key: &MarketItemId) {
-        self.dict.remove::<U256>(&key.to_string());
-    }
-}
-
-pub struct ItemStatusData {
-    dict: Dict,
-}
-
-impl ItemStatusData {
-    pub fn instance() -> ItemStatusData {
-        ItemStatusData {
-            dict: Dict::instance(ITEM_STATUS_DATA),
-        }
-    }
-
-    pub fn init() {
-        Dict::init(ITEM_STATUS_DATA)
-    }
-
-    pub fn get(&self, key: &MarketItemId) -> Option<String> {
-        self.dict.get(&key.to_string())
-    }
-
-    pub fn set(&self, key: &MarketItemId, value: String) {
-        self.dict.set(&key.to_string(), value);
-    }
-
-    pub fn remove(&self, key: &MarketItemId) {
-        self.dict.remove::<String>(&key.to_string());
-    }
-}
-
 pub struct OwnedTokens {
     tokens_dict: Dict,
     indexes_dict: Dict,
@@ -284,6 +187,504 @@ impl OwnedTokens {
     }
 }
 
+/// Append-only record of each seller's closed listings.
+pub struct SellerHistory {
+    items_dict: Dict,
+    counts_dict: Dict,
+}
+
+impl SellerHistory {
+    pub fn instance() -> SellerHistory {
+        SellerHistory {
+            items_dict: Dict::instance(SELLER_HISTORY_BY_INDEX_DICT),
+            counts_dict: Dict::instance(SELLER_HISTORY_COUNTS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(SELLER_HISTORY_BY_INDEX_DICT);
+        Dict::init(SELLER_HISTORY_COUNTS_DICT);
+    }
+
+    pub fn get_item_by_index(&self, owner: &Key, index: &U256) -> Option<MarketItemId> {
+        self.items_dict.get(&key_and_value_to_str(owner, index))
+    }
+
+    pub fn get_count(&self, owner: &Key) -> U256 {
+        self.counts_dict
+            .get(&key_to_str(owner))
+            .unwrap_or_default()
+    }
+
+    pub fn push(&self, owner: &Key, value: &MarketItemId) {
+        let length = self.get_count(owner);
+        self.items_dict
+            .set(&key_and_value_to_str(owner, &length), *value);
+        self.counts_dict.set(&key_to_str(owner), length + 1);
+    }
+}
+
+/// Enumerable set of the listings that can currently be bought.
+pub struct ActiveItems {
+    items_dict: Dict,
+    indexes_dict: Dict,
+}
+
+impl ActiveItems {
+    pub fn instance() -> ActiveItems {
+        ActiveItems {
+            items_dict: Dict::instance(ACTIVE_ITEMS_BY_INDEX_DICT),
+            indexes_dict: Dict::instance(ACTIVE_INDEXES_BY_ITEM_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(ACTIVE_ITEMS_BY_INDEX_DICT);
+        Dict::init(ACTIVE_INDEXES_BY_ITEM_DICT);
+        set_key(ACTIVE_ITEMS_COUNT, U256::zero());
+    }
+
+    pub fn len(&self) -> U256 {
+        get_key(ACTIVE_ITEMS_COUNT).unwrap_or_default()
+    }
+
+    fn set_len(&self, value: U256) {
+        set_key(ACTIVE_ITEMS_COUNT, value);
+    }
+
+    pub fn get_item_by_index(&self, index: &U256) -> Option<MarketItemId> {
+        self.items_dict.get(&index.to_string())
+    }
+
+    pub fn get_index_by_item(&self, value: &MarketItemId) -> Option<U256> {
+        self.indexes_dict.get(&value.to_string())
+    }
+
+    pub fn add(&self, value: &MarketItemId) {
+        let length = self.len();
+        self.indexes_dict.set(&value.to_string(), length);
+        self.items_dict.set(&length.to_string(), *value);
+        self.set_len(length + 1);
+    }
+
+    pub fn remove(&self, value: &MarketItemId) {
+        let index = match self.get_index_by_item(value) {
+            Some(index) => index,
+            None => return,
+        };
+        let last_index = self.len() - 1;
+        if index != last_index {
+            let last = self.get_item_by_index(&last_index).unwrap_or_revert();
+            self.indexes_dict.set(&last.to_string(), index);
+            self.items_dict.set(&index.to_string(), last);
+        }
+        self.items_dict.remove::<MarketItemId>(&last_index.to_string());
+        self.indexes_dict.remove::<U256>(&value.to_string());
+        self.set_len(last_index);
+    }
+}
+
+/// Active listings grouped by the NFT contract they belong to.
+pub struct CollectionItems {
+    items_dict: Dict,
+    indexes_dict: Dict,
+    counts_dict: Dict,
+}
+
+impl CollectionItems {
+    pub fn instance() -> CollectionItems {
+        CollectionItems {
+            items_dict: Dict::instance(COLLECTION_ITEMS_BY_INDEX_DICT),
+            indexes_dict: Dict::instance(COLLECTION_INDEXES_BY_ITEM_DICT),
+            counts_dict: Dict::instance(COLLECTION_ITEM_COUNTS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(COLLECTION_ITEMS_BY_INDEX_DICT);
+        Dict::init(COLLECTION_INDEXES_BY_ITEM_DICT);
+        Dict::init(COLLECTION_ITEM_COUNTS_DICT);
+    }
+
+    pub fn get_item_by_index(
+        &self,
+        collection: &NFTContractAddress,
+        index: &U256,
+    ) -> Option<MarketItemId> {
+        self.items_dict
+            .get(&key_and_value_to_str(&collection_key(collection), index))
+    }
+
+    pub fn get_index_by_item(
+        &self,
+        collection: &NFTContractAddress,
+        value: &MarketItemId,
+    ) -> Option<U256> {
+        self.indexes_dict
+            .get(&key_and_value_to_str(&collection_key(collection), value))
+    }
+
+    pub fn get_count(&self, collection: &NFTContractAddress) -> U256 {
+        self.counts_dict
+            .get(&key_to_str(&collection_key(collection)))
+            .unwrap_or_default()
+    }
+
+    fn set_count(&self, collection: &NFTContractAddress, value: U256) {
+        self.counts_dict
+            .set(&key_to_str(&collection_key(collection)), value);
+    }
+
+    pub fn add(&self, collection: &NFTContractAddress, value: &MarketItemId) {
+        let key = collection_key(collection);
+        let length = self.get_count(collection);
+        self.indexes_dict
+            .set(&key_and_value_to_str(&key, value), length);
+        self.items_dict
+            .set(&key_and_value_to_str(&key, &length), *value);
+        self.set_count(collection, length + 1);
+    }
+
+    pub fn remove(&self, collection: &NFTContractAddress, value: &MarketItemId) {
+        let key = collection_key(collection);
+        let index = match self.get_index_by_item(collection, value) {
+            Some(index) => index,
+            None => return,
+        };
+        let last_index = self.get_count(collection) - 1;
+        if index != last_index {
+            let last = self
+                .get_item_by_index(collection, &last_index)
+                .unwrap_or_revert();
+            self.indexes_dict
+                .set(&key_and_value_to_str(&key, &last), index);
+            self.items_dict
+                .set(&key_and_value_to_str(&key, &index), last);
+        }
+        self.items_dict
+            .remove::<MarketItemId>(&key_and_value_to_str(&key, &last_index));
+        self.indexes_dict
+            .remove::<U256>(&key_and_value_to_str(&key, value));
+        self.set_count(collection, last_index);
+    }
+}
+
+/// Append-only sale history of each (collection, token id) pair.
+pub struct TokenSales {
+    sales_dict: Dict,
+    counts_dict: Dict,
+}
+
+impl TokenSales {
+    pub fn instance() -> TokenSales {
+        TokenSales {
+            sales_dict: Dict::instance(TOKEN_SALES_BY_INDEX_DICT),
+            counts_dict: Dict::instance(TOKEN_SALE_COUNTS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(TOKEN_SALES_BY_INDEX_DICT);
+        Dict::init(TOKEN_SALE_COUNTS_DICT);
+    }
+
+    pub fn get_sale_by_index(
+        &self,
+        collection: &NFTContractAddress,
+        token_id: &TokenId,
+        index: &U256,
+    ) -> Option<SaleRecord> {
+        self.sales_dict.get(&key_and_value_to_str(
+            &collection_key(collection),
+            &(*token_id, *index),
+        ))
+    }
+
+    pub fn get_count(&self, collection: &NFTContractAddress, token_id: &TokenId) -> U256 {
+        self.counts_dict
+            .get(&key_and_value_to_str(&collection_key(collection), token_id))
+            .unwrap_or_default()
+    }
+
+    pub fn push(&self, collection: &NFTContractAddress, token_id: &TokenId, value: SaleRecord) {
+        let key = collection_key(collection);
+        let length = self.get_count(collection, token_id);
+        self.sales_dict
+            .set(&key_and_value_to_str(&key, &(*token_id, length)), value);
+        self.counts_dict
+            .set(&key_and_value_to_str(&key, token_id), length + 1);
+    }
+}
+
+pub struct CollectionStatsData {
+    dict: Dict,
+}
+
+impl CollectionStatsData {
+    pub fn instance() -> CollectionStatsData {
+        CollectionStatsData {
+            dict: Dict::instance(COLLECTION_STATS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(COLLECTION_STATS_DICT)
+    }
+
+    pub fn get(&self, collection: &NFTContractAddress) -> CollectionStats {
+        self.dict
+            .get(&key_to_str(&collection_key(collection)))
+            .unwrap_or_default()
+    }
+
+    pub fn set(&self, collection: &NFTContractAddress, value: CollectionStats) {
+        self.dict.set(&key_to_str(&collection_key(collection)), value);
+    }
+}
+
+/// Active listings of each collection kept as a binary min-heap on asking price, so the floor
+/// listing always sits at index 0 and adding or removing a listing touches O(log n) entries.
+pub struct FloorPrices {
+    entries_dict: Dict,
+    positions_dict: Dict,
+    sizes_dict: Dict,
+}
+
+impl FloorPrices {
+    pub fn instance() -> FloorPrices {
+        FloorPrices {
+            entries_dict: Dict::instance(FLOOR_HEAP_ENTRIES_DICT),
+            positions_dict: Dict::instance(FLOOR_HEAP_POSITIONS_DICT),
+            sizes_dict: Dict::instance(FLOOR_HEAP_SIZES_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(FLOOR_HEAP_ENTRIES_DICT);
+        Dict::init(FLOOR_HEAP_POSITIONS_DICT);
+        Dict::init(FLOOR_HEAP_SIZES_DICT);
+    }
+
+    pub fn get_size(&self, collection: &NFTContractAddress) -> U256 {
+        self.sizes_dict
+            .get(&key_to_str(&collection_key(collection)))
+            .unwrap_or_default()
+    }
+
+    fn set_size(&self, collection: &NFTContractAddress, value: U256) {
+        self.sizes_dict
+            .set(&key_to_str(&collection_key(collection)), value);
+    }
+
+    /// The (asking price, item id) pair at `index` of the collection's heap.
+    pub fn get_entry(
+        &self,
+        collection: &NFTContractAddress,
+        index: &U256,
+    ) -> Option<(U512, MarketItemId)> {
+        self.entries_dict
+            .get(&key_and_value_to_str(&collection_key(collection), index))
+    }
+
+    /// The cheapest active listing of the collection.
+    pub fn floor(&self, collection: &NFTContractAddress) -> Option<(U512, MarketItemId)> {
+        self.get_entry(collection, &U256::zero())
+    }
+
+    /// The cheapest listing of the collection that `accept` admits. Entries are visited in price
+    /// order, and the search gives up after rejecting `max_rejected` of them.
+    pub fn lowest<F>(
+        &self,
+        collection: &NFTContractAddress,
+        max_rejected: u64,
+        accept: F,
+    ) -> Option<(U512, MarketItemId)>
+    where
+        F: Fn(&MarketItemId) -> bool,
+    {
+        let length = self.get_size(collection);
+        // Heap positions whose parent has been rejected; the next cheapest entry is among them.
+        let mut frontier: Vec<(U256, (U512, MarketItemId))> = Vec::new();
+        if let Some(root) = self.floor(collection) {
+            frontier.push((U256::zero(), root));
+        }
+        let mut rejected = 0;
+        while rejected < max_rejected {
+            let cheapest = frontier
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, (_, entry))| entry.0)
+                .map(|(position, _)| position)?;
+            let (index, entry) = frontier.swap_remove(cheapest);
+            if accept(&entry.1) {
+                return Some(entry);
+            }
+            rejected += 1;
+            for child in &[index * 2 + 1, index * 2 + 2] {
+                if *child < length {
+                    frontier.push((*child, self.get_entry(collection, child).unwrap_or_revert()));
+                }
+            }
+        }
+        None
+    }
+
+    pub fn push(&self, collection: &NFTContractAddress, item_id: &MarketItemId, price: U512) {
+        let length = self.get_size(collection);
+        self.set_size(collection, length + 1);
+        self.sift_up(collection, length, (price, *item_id));
+    }
+
+    pub fn remove(&self, collection: &NFTContractAddress, item_id: &MarketItemId) {
+        let key = collection_key(collection);
+        let index: U256 = match self
+            .positions_dict
+            .get(&key_and_value_to_str(&key, item_id))
+        {
+            Some(index) => index,
+            None => return,
+        };
+        let last_index = self.get_size(collection) - 1;
+        let last = self.get_entry(collection, &last_index).unwrap_or_revert();
+        self.entries_dict
+            .remove::<(U512, MarketItemId)>(&key_and_value_to_str(&key, &last_index));
+        self.positions_dict
+            .remove::<U256>(&key_and_value_to_str(&key, item_id));
+        self.set_size(collection, last_index);
+        if index == last_index {
+            return;
+        }
+        // Move the last entry into the gap, then restore the heap order around it.
+        let parent_is_dearer = !index.is_zero()
+            && self
+                .get_entry(collection, &((index - 1) / 2))
+                .unwrap_or_revert()
+                .0
+                > last.0;
+        if parent_is_dearer {
+            self.sift_up(collection, index, last);
+        } else {
+            self.sift_down(collection, last_index, index, last);
+        }
+    }
+
+    fn put(&self, collection: &NFTContractAddress, index: U256, entry: (U512, MarketItemId)) {
+        let key = collection_key(collection);
+        self.positions_dict
+            .set(&key_and_value_to_str(&key, &entry.1), index);
+        self.entries_dict
+            .set(&key_and_value_to_str(&key, &index), entry);
+    }
+
+    fn sift_up(
+        &self,
+        collection: &NFTContractAddress,
+        mut index: U256,
+        entry: (U512, MarketItemId),
+    ) {
+        while !index.is_zero() {
+            let parent = (index - 1) / 2;
+            let parent_entry = self.get_entry(collection, &parent).unwrap_or_revert();
+            if parent_entry.0 <= entry.0 {
+                break;
+            }
+            self.put(collection, index, parent_entry);
+            index = parent;
+        }
+        self.put(collection, index, entry);
+    }
+
+    fn sift_down(
+        &self,
+        collection: &NFTContractAddress,
+        length: U256,
+        mut index: U256,
+        entry: (U512, MarketItemId),
+    ) {
+        loop {
+            let left = index * 2 + 1;
+            if left >= length {
+                break;
+            }
+            let mut child = left;
+            let mut child_entry = self.get_entry(collection, &left).unwrap_or_revert();
+            let right = left + 1;
+            if right < length {
+                let right_entry = self.get_entry(collection, &right).unwrap_or_revert();
+                if right_entry.0 < child_entry.0 {
+                    child = right;
+                    child_entry = right_entry;
+                }
+            }
+            if entry.0 <= child_entry.0 {
+                break;
+            }
+            self.put(collection, index, child_entry);
+            index = child;
+        }
+        self.put(collection, index, entry);
+    }
+}
+
+/// Optional CEP-47 extensions each collection supports, as set by a market admin. Collections
+/// without an entry have never been flagged.
+pub struct CollectionCapabilities {
+    dict: Dict,
+}
+
+impl CollectionCapabilities {
+    pub fn instance() -> CollectionCapabilities {
+        CollectionCapabilities {
+            dict: Dict::instance(COLLECTION_CAPABILITIES_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(COLLECTION_CAPABILITIES_DICT)
+    }
+
+    pub fn get(&self, collection: &NFTContractAddress) -> Option<u8> {
+        self.dict.get(&key_to_str(&collection_key(collection)))
+    }
+
+    pub fn set(&self, collection: &NFTContractAddress, value: u8) {
+        self.dict.set(&key_to_str(&collection_key(collection)), value);
+    }
+}
+
+fn collection_key(collection: &NFTContractAddress) -> Key {
+    Key::Hash(collection.value())
+}
+
+/// Listings whose token the market holds itself, having received it through a safe transfer.
+pub struct CustodyItems {
+    dict: Dict,
+}
+
+impl CustodyItems {
+    pub fn instance() -> CustodyItems {
+        CustodyItems {
+            dict: Dict::instance(CUSTODY_ITEMS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(CUSTODY_ITEMS_DICT)
+    }
+
+    pub fn contains(&self, item_id: &MarketItemId) -> bool {
+        self.dict.get(&item_id.to_string()).unwrap_or_default()
+    }
+
+    pub fn add(&self, item_id: &MarketItemId) {
+        self.dict.set(&item_id.to_string(), true);
+    }
+
+    pub fn remove(&self, item_id: &MarketItemId) {
+        self.dict.remove::<bool>(&item_id.to_string());
+    }
+}
+
 pub struct Allowances {
     dict: Dict,
 }
@@ -349,6 +750,64 @@ pub fn set_total_supply(total_supply: U256) {
     set_key(TOTAL_SUPPLY, total_supply);
 }
 
+pub fn next_item_id() -> MarketItemId {
+    get_key(NEXT_ITEM_ID).unwrap_or_default()
+}
+
+pub fn set_next_item_id(item_id: MarketItemId) {
+    set_key(NEXT_ITEM_ID, item_id);
+}
+
+pub fn fee_basis_points() -> u32 {
+    get_key(FEE_BASIS_POINTS).unwrap_or_default()
+}
+
+pub fn set_fee_basis_points(fee_basis_points: u32) {
+    set_key(FEE_BASIS_POINTS, fee_basis_points);
+}
+
+pub fn fee_recipient() -> Key {
+    get_key(FEE_RECIPIENT).unwrap_or_revert()
+}
+
+pub fn set_fee_recipient(fee_recipient: Key) {
+    set_key(FEE_RECIPIENT, fee_recipient);
+}
+
+pub fn schema_version() -> u32 {
+    get_key(SCHEMA_VERSION).unwrap_or_default()
+}
+
+pub fn set_schema_version(schema_version: u32) {
+    set_key(SCHEMA_VERSION, schema_version);
+}
+
+pub fn contract_hash() -> ContractHash {
+    let call_stacks = get_call_stack();
+    let last_entry = call_stacks.last().unwrap_or_revert();
+    let contract_hash: Option<ContractHash> = match last_entry {
+        CallStackElement::StoredContract {
+            contract_package_hash: _,
+            contract_hash,
+        } => Some(*contract_hash),
+        _ => None,
+    };
+    contract_hash.unwrap_or_revert()
+}
+
+/// Hash of the contract that called the current entry point, if a contract did.
+pub fn caller_contract_hash() -> Option<ContractHash> {
+    let call_stacks = get_call_stack();
+    let caller_entry = call_stacks.get(call_stacks.len().checked_sub(2)?)?;
+    match caller_entry {
+        CallStackElement::StoredContract {
+            contract_package_hash: _,
+            contract_hash,
+        } => Some(*contract_hash),
+        _ => None,
+    }
+}
+
 pub fn contract_package_hash() -> ContractPackageHash {
     let call_stacks = get_call_stack();
     let last_entry = call_stacks.last().unwrap_or_revert();
@@ -362,4 +821,7 @@ pub fn contract_package_hash() -> ContractPackageHash {
     package_hash.unwrap_or_revert()
 }
 
-pub fn emit(_event: &MarketEvent) {}
+/// Records `event` in the Casper Event Standard log, the market's only event record.
+pub fn emit(event: &MarketEvent) {
+    CesEvents::instance().emit(event.name(), event);
+}
@@ -1,7 +1,9 @@
 use alloc::vec::Vec;
+use casper_types::bytesrepr::{self, ToBytes};
 use casper_types::Key;
+use contract_utils::{Schema, Schemas};
 
-use crate::MarketItemId;
+use crate::{MarketItemId, MarketItemStatus};
 
 pub enum MarketEvent {
     CreateItem {
@@ -12,4 +14,131 @@ pub enum MarketEvent {
         recipient: Key,
         item_id: MarketItemId,
     },
+    CancelItem {
+        seller: Key,
+        item_id: MarketItemId,
+    },
+    InvalidateItem {
+        item_id: MarketItemId,
+    },
+    /// A status set directly by a market admin.
+    SetItemStatus {
+        item_id: MarketItemId,
+        status: MarketItemStatus,
+    },
+    SetFeeConfig {
+        fee_basis_points: u32,
+        fee_recipient: Key,
+    },
+}
+
+impl MarketEvent {
+    /// Name the event is registered under in the Casper Event Standard schemas.
+    pub fn name(&self) -> &'static str {
+        match self {
+            MarketEvent::CreateItem { .. } => "CreateItem",
+            MarketEvent::SoldItem { .. } => "SoldItem",
+            MarketEvent::CancelItem { .. } => "CancelItem",
+            MarketEvent::InvalidateItem { .. } => "InvalidateItem",
+            MarketEvent::SetItemStatus { .. } => "SetItemStatus",
+            MarketEvent::SetFeeConfig { .. } => "SetFeeConfig",
+        }
+    }
+
+    pub fn schemas() -> Schemas {
+        Schemas::new()
+            .with(
+                "CreateItem",
+                Schema::new()
+                    .with_elem::<Key>("recipient")
+                    .with_elem::<Vec<MarketItemId>>("item_ids"),
+            )
+            .with(
+                "SoldItem",
+                Schema::new()
+                    .with_elem::<Key>("recipient")
+                    .with_elem::<MarketItemId>("item_id"),
+            )
+            .with(
+                "CancelItem",
+                Schema::new()
+                    .with_elem::<Key>("seller")
+                    .with_elem::<MarketItemId>("item_id"),
+            )
+            .with(
+                "InvalidateItem",
+                Schema::new().with_elem::<MarketItemId>("item_id"),
+            )
+            .with(
+                "SetItemStatus",
+                Schema::new()
+                    .with_elem::<MarketItemId>("item_id")
+                    .with_elem::<MarketItemStatus>("status"),
+            )
+            .with(
+                "SetFeeConfig",
+                Schema::new()
+                    .with_elem::<u32>("fee_basis_points")
+                    .with_elem::<Key>("fee_recipient"),
+            )
+    }
+}
+
+/// Encodes the variant's fields in declaration order; the variant itself is identified by the
+/// [`MarketEvent::name`] CES stores ahead of them.
+impl ToBytes for MarketEvent {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        match self {
+            MarketEvent::CreateItem { recipient, item_ids } => {
+                result.append(&mut recipient.to_bytes()?);
+                result.append(&mut item_ids.to_bytes()?);
+            }
+            MarketEvent::SoldItem { recipient, item_id } => {
+                result.append(&mut recipient.to_bytes()?);
+                result.append(&mut item_id.to_bytes()?);
+            }
+            MarketEvent::CancelItem { seller, item_id } => {
+                result.append(&mut seller.to_bytes()?);
+                result.append(&mut item_id.to_bytes()?);
+            }
+            MarketEvent::InvalidateItem { item_id } => {
+                result.append(&mut item_id.to_bytes()?);
+            }
+            MarketEvent::SetItemStatus { item_id, status } => {
+                result.append(&mut item_id.to_bytes()?);
+                result.append(&mut status.to_bytes()?);
+            }
+            MarketEvent::SetFeeConfig {
+                fee_basis_points,
+                fee_recipient,
+            } => {
+                result.append(&mut fee_basis_points.to_bytes()?);
+                result.append(&mut fee_recipient.to_bytes()?);
+            }
+        }
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        match self {
+            MarketEvent::CreateItem { recipient, item_ids } => {
+                recipient.serialized_length() + item_ids.serialized_length()
+            }
+            MarketEvent::SoldItem { recipient, item_id } => {
+                recipient.serialized_length() + item_id.serialized_length()
+            }
+            MarketEvent::CancelItem { seller, item_id } => {
+                seller.serialized_length() + item_id.serialized_length()
+            }
+            MarketEvent::InvalidateItem { item_id } => item_id.serialized_length(),
+            MarketEvent::SetItemStatus { item_id, status } => {
+                item_id.serialized_length() + status.serialized_length()
+            }
+            MarketEvent::SetFeeConfig {
+                fee_basis_points,
+                fee_recipient,
+            } => fee_basis_points.serialized_length() + fee_recipient.serialized_length(),
+        }
+    }
 }
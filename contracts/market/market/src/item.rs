@@ -0,0 +1,253 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use casper_types::bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH};
+use casper_types::{CLType, CLTyped, Key, U256, U512};
+
+use crate::{MarketItemId, NFTContractAddress, TokenId};
+
+/// Lifecycle state of a listing, stored on chain as a single `u8`.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MarketItemStatus {
+    Available = 0,
+    Sold = 1,
+    Cancelled = 2,
+    Expired = 3,
+    Invalid = 4,
+    InAuction = 5,
+}
+
+impl MarketItemStatus {
+    /// Whether the listing is closed for good.
+    pub fn is_final(self) -> bool {
+        !matches!(self, MarketItemStatus::Available | MarketItemStatus::InAuction)
+    }
+
+    /// Transition table for listings. Only open listings (`Available` and
+    /// `InAuction`) may move; every other status is final.
+    pub fn can_transition_to(self, next: MarketItemStatus) -> bool {
+        use MarketItemStatus::*;
+        matches!(
+            (self, next),
+            (Available, Sold)
+                | (Available, Cancelled)
+                | (Available, Expired)
+                | (Available, Invalid)
+                | (Available, InAuction)
+                | (InAuction, Available)
+                | (InAuction, Sold)
+                | (InAuction, Cancelled)
+                | (InAuction, Expired)
+                | (InAuction, Invalid)
+        )
+    }
+}
+
+impl CLTyped for MarketItemStatus {
+    fn cl_type() -> CLType {
+        CLType::U8
+    }
+}
+
+impl FromBytes for MarketItemStatus {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, bytes) = u8::from_bytes(bytes)?;
+        let value = match tag {
+            0 => MarketItemStatus::Available,
+            1 => MarketItemStatus::Sold,
+            2 => MarketItemStatus::Cancelled,
+            3 => MarketItemStatus::Expired,
+            4 => MarketItemStatus::Invalid,
+            5 => MarketItemStatus::InAuction,
+            _ => return Err(bytesrepr::Error::Formatting),
+        };
+        Ok((value, bytes))
+    }
+}
+
+impl ToBytes for MarketItemStatus {
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        Ok(vec![*self as u8])
+    }
+}
+
+/// A single listing, stored as one record in the `market_items` dictionary.
+///
+/// Serialized as the tuple `((item_id, seller, nft_contract_address), (token_id, asking_price,
+/// status))`.
+#[derive(Clone)]
+pub struct MarketItem {
+    pub item_id: MarketItemId,
+    pub seller: Key,
+    pub nft_contract_address: NFTContractAddress,
+    pub token_id: TokenId,
+    pub asking_price: U512,
+    pub status: MarketItemStatus,
+}
+
+impl CLTyped for MarketItem {
+    fn cl_type() -> CLType {
+        <(
+            (MarketItemId, Key, NFTContractAddress),
+            (TokenId, U512, MarketItemStatus),
+        )>::cl_type()
+    }
+}
+
+impl FromBytes for MarketItem {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (item_id, bytes) = FromBytes::from_bytes(bytes)?;
+        let (seller, bytes) = FromBytes::from_bytes(bytes)?;
+        let (nft_contract_address, bytes) = FromBytes::from_bytes(bytes)?;
+        let (token_id, bytes) = FromBytes::from_bytes(bytes)?;
+        let (asking_price, bytes) = FromBytes::from_bytes(bytes)?;
+        let (status, bytes) = FromBytes::from_bytes(bytes)?;
+        let value = MarketItem {
+            item_id,
+            seller,
+            nft_contract_address,
+            token_id,
+            asking_price,
+            status,
+        };
+        Ok((value, bytes))
+    }
+}
+
+impl ToBytes for MarketItem {
+    fn serialized_length(&self) -> usize {
+        let mut size = 0;
+        size += self.item_id.serialized_length();
+        size += self.seller.serialized_length();
+        size += self.nft_contract_address.serialized_length();
+        size += self.token_id.serialized_length();
+        size += self.asking_price.serialized_length();
+        size += self.status.serialized_length();
+        size
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut vec = Vec::with_capacity(self.serialized_length());
+        vec.append(&mut self.item_id.to_bytes()?);
+        vec.append(&mut self.seller.to_bytes()?);
+        vec.append(&mut self.nft_contract_address.to_bytes()?);
+        vec.append(&mut self.token_id.to_bytes()?);
+        vec.append(&mut self.asking_price.to_bytes()?);
+        vec.append(&mut self.status.to_bytes()?);
+        Ok(vec)
+    }
+}
+
+/// A completed sale, appended to the per-token sale history.
+///
+/// Serialized as the tuple `((item_id, seller, buyer), (price, timestamp))`.
+pub struct SaleRecord {
+    pub item_id: MarketItemId,
+    pub seller: Key,
+    pub buyer: Key,
+    pub price: U512,
+    pub timestamp: u64,
+}
+
+impl CLTyped for SaleRecord {
+    fn cl_type() -> CLType {
+        <((MarketItemId, Key, Key), (U512, u64))>::cl_type()
+    }
+}
+
+impl FromBytes for SaleRecord {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (item_id, bytes) = FromBytes::from_bytes(bytes)?;
+        let (seller, bytes) = FromBytes::from_bytes(bytes)?;
+        let (buyer, bytes) = FromBytes::from_bytes(bytes)?;
+        let (price, bytes) = FromBytes::from_bytes(bytes)?;
+        let (timestamp, bytes) = FromBytes::from_bytes(bytes)?;
+        let value = SaleRecord {
+            item_id,
+            seller,
+            buyer,
+            price,
+            timestamp,
+        };
+        Ok((value, bytes))
+    }
+}
+
+impl ToBytes for SaleRecord {
+    fn serialized_length(&self) -> usize {
+        let mut size = 0;
+        size += self.item_id.serialized_length();
+        size += self.seller.serialized_length();
+        size += self.buyer.serialized_length();
+        size += self.price.serialized_length();
+        size += self.timestamp.serialized_length();
+        size
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut vec = Vec::with_capacity(self.serialized_length());
+        vec.append(&mut self.item_id.to_bytes()?);
+        vec.append(&mut self.seller.to_bytes()?);
+        vec.append(&mut self.buyer.to_bytes()?);
+        vec.append(&mut self.price.to_bytes()?);
+        vec.append(&mut self.timestamp.to_bytes()?);
+        Ok(vec)
+    }
+}
+
+/// Aggregate market figures for a single NFT collection.
+///
+/// Serialized as the tuple `((volume, sales_count), (active_listings, floor_price))`.
+#[derive(Default)]
+pub struct CollectionStats {
+    pub volume: U512,
+    pub sales_count: U256,
+    pub active_listings: U256,
+    pub floor_price: Option<U512>,
+}
+
+impl CLTyped for CollectionStats {
+    fn cl_type() -> CLType {
+        <((U512, U256), (U256, Option<U512>))>::cl_type()
+    }
+}
+
+impl FromBytes for CollectionStats {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (volume, bytes) = FromBytes::from_bytes(bytes)?;
+        let (sales_count, bytes) = FromBytes::from_bytes(bytes)?;
+        let (active_listings, bytes) = FromBytes::from_bytes(bytes)?;
+        let (floor_price, bytes) = FromBytes::from_bytes(bytes)?;
+        let value = CollectionStats {
+            volume,
+            sales_count,
+            active_listings,
+            floor_price,
+        };
+        Ok((value, bytes))
+    }
+}
+
+impl ToBytes for CollectionStats {
+    fn serialized_length(&self) -> usize {
+        let mut size = 0;
+        size += self.volume.serialized_length();
+        size += self.sales_count.serialized_length();
+        size += self.active_listings.serialized_length();
+        size += self.floor_price.serialized_length();
+        size
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut vec = Vec::with_capacity(self.serialized_length());
+        vec.append(&mut self.volume.to_bytes()?);
+        vec.append(&mut self.sales_count.to_bytes()?);
+        vec.append(&mut self.active_listings.to_bytes()?);
+        vec.append(&mut self.floor_price.to_bytes()?);
+        Ok(vec)
+    }
+}
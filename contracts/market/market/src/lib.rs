@@ -3,13 +3,15 @@ extern crate alloc;
 
 use alloc::{collections::BTreeMap, string::String};
 use alloc::vec::Vec;
-use casper_types::{CLType, CLTyped, ContractHash, U256};
+use casper_types::{CLType, CLTyped, ContractHash, ContractPackageHash, Key, U256, U512};
 use casper_types::bytesrepr::{FromBytes, ToBytes};
 pub use contract_utils;
+pub use item::{CollectionStats, MarketItem, MarketItemStatus, SaleRecord};
 pub use market::{Error, MarketContract};
 
 pub mod data;
 pub mod event;
+mod item;
 mod market;
 
 pub type MarketItemId = U256;
@@ -17,37 +19,132 @@ pub type TokenId = U256;
 pub type NFTContractAddress = ContractHash;
 pub type Meta = BTreeMap<String, String>;
 
-pub const ITEM_STATUS_AVAILABLE: &str = "available";
-pub const ITEM_STATUS_CANCELLED: &str = "cancelled";
-pub const ITEM_STATUS_SOLD: &str = "sold";
-
-//TODO
-// pub struct MarketItemList {
-//     ids: Vec<U256>
-// }
-// impl CLTyped for MarketItemList {
-//     fn cl_type() -> CLType {
-//         CLType::Any
-//     }
-// }
-// impl FromBytes for MarketItemList {
-//     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), casper_types::bytesrepr::Error> {
-//         let (ids, bytes) = FromBytes::from_bytes(bytes)?;
-//         let value = MarketItemList{
-//             ids
-//         };
-//         Ok((value, bytes))
-//     }
-// }
-// impl ToBytes for MarketItemList {
-//     fn serialized_length(&self) -> usize {
-//         let mut size = 0;
-//         size += self.ids.serialized_length();
-//         size
-//     }
-//     fn to_bytes(&self) -> Result<Vec<u8>, casper_types::bytesrepr::Error> {
-//         let mut vec = Vec::with_capacity(self.serialized_length());
-//         vec.append(&mut self.ids.to_bytes()?);
-//         Ok(vec)
-//     }
-// }
+/// Outcome of `process_market_sale`, returned to the calling session or contract.
+///
+/// Serialized as the tuple `((item_id, seller, buyer), (price, fee, royalty))`, which callers
+/// can decode without depending on this crate.
+pub struct SaleReceipt {
+    pub item_id: MarketItemId,
+    pub seller: Key,
+    pub buyer: Key,
+    pub price: U512,
+    pub fee: U512,
+    pub royalty: U512,
+}
+
+impl CLTyped for SaleReceipt {
+    fn cl_type() -> CLType {
+        <((MarketItemId, Key, Key), (U512, U512, U512))>::cl_type()
+    }
+}
+
+impl FromBytes for SaleReceipt {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), casper_types::bytesrepr::Error> {
+        let (item_id, bytes) = FromBytes::from_bytes(bytes)?;
+        let (seller, bytes) = FromBytes::from_bytes(bytes)?;
+        let (buyer, bytes) = FromBytes::from_bytes(bytes)?;
+        let (price, bytes) = FromBytes::from_bytes(bytes)?;
+        let (fee, bytes) = FromBytes::from_bytes(bytes)?;
+        let (royalty, bytes) = FromBytes::from_bytes(bytes)?;
+        let value = SaleReceipt {
+            item_id,
+            seller,
+            buyer,
+            price,
+            fee,
+            royalty,
+        };
+        Ok((value, bytes))
+    }
+}
+
+impl ToBytes for SaleReceipt {
+    fn serialized_length(&self) -> usize {
+        let mut size = 0;
+        size += self.item_id.serialized_length();
+        size += self.seller.serialized_length();
+        size += self.buyer.serialized_length();
+        size += self.price.serialized_length();
+        size += self.fee.serialized_length();
+        size += self.royalty.serialized_length();
+        size
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, casper_types::bytesrepr::Error> {
+        let mut vec = Vec::with_capacity(self.serialized_length());
+        vec.append(&mut self.item_id.to_bytes()?);
+        vec.append(&mut self.seller.to_bytes()?);
+        vec.append(&mut self.buyer.to_bytes()?);
+        vec.append(&mut self.price.to_bytes()?);
+        vec.append(&mut self.fee.to_bytes()?);
+        vec.append(&mut self.royalty.to_bytes()?);
+        Ok(vec)
+    }
+}
+
+/// Snapshot of the market's identity, fee configuration and listing counts.
+///
+/// Serialized as the tuple `((contract_hash, contract_package_hash), (fee_basis_points,
+/// fee_recipient), (total_supply, active_listings))`.
+pub struct MarketInfo {
+    pub contract_hash: ContractHash,
+    pub contract_package_hash: ContractPackageHash,
+    pub fee_basis_points: u32,
+    pub fee_recipient: Key,
+    pub total_supply: U256,
+    pub active_listings: U256,
+}
+
+impl CLTyped for MarketInfo {
+    fn cl_type() -> CLType {
+        <(
+            (ContractHash, ContractPackageHash),
+            (u32, Key),
+            (U256, U256),
+        )>::cl_type()
+    }
+}
+
+impl FromBytes for MarketInfo {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), casper_types::bytesrepr::Error> {
+        let (contract_hash, bytes) = FromBytes::from_bytes(bytes)?;
+        let (contract_package_hash, bytes) = FromBytes::from_bytes(bytes)?;
+        let (fee_basis_points, bytes) = FromBytes::from_bytes(bytes)?;
+        let (fee_recipient, bytes) = FromBytes::from_bytes(bytes)?;
+        let (total_supply, bytes) = FromBytes::from_bytes(bytes)?;
+        let (active_listings, bytes) = FromBytes::from_bytes(bytes)?;
+        let value = MarketInfo {
+            contract_hash,
+            contract_package_hash,
+            fee_basis_points,
+            fee_recipient,
+            total_supply,
+            active_listings,
+        };
+        Ok((value, bytes))
+    }
+}
+
+impl ToBytes for MarketInfo {
+    fn serialized_length(&self) -> usize {
+        let mut size = 0;
+        size += self.contract_hash.serialized_length();
+        size += self.contract_package_hash.serialized_length();
+        size += self.fee_basis_points.serialized_length();
+        size += self.fee_recipient.serialized_length();
+        size += self.total_supply.serialized_length();
+        size += self.active_listings.serialized_length();
+        size
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, casper_types::bytesrepr::Error> {
+        let mut vec = Vec::with_capacity(self.serialized_length());
+        vec.append(&mut self.contract_hash.to_bytes()?);
+        vec.append(&mut self.contract_package_hash.to_bytes()?);
+        vec.append(&mut self.fee_basis_points.to_bytes()?);
+        vec.append(&mut self.fee_recipient.to_bytes()?);
+        vec.append(&mut self.total_supply.to_bytes()?);
+        vec.append(&mut self.active_listings.to_bytes()?);
+        Ok(vec)
+    }
+}
@@ -1,14 +1,23 @@
-use alloc::string::{String, ToString};
+use alloc::string::String;
 use alloc::vec;
 use alloc::vec::Vec;
 use casper_contract::contract_api::{runtime, storage, system};
 use casper_contract::unwrap_or_revert::UnwrapOrRevert;
 use casper_types::{ApiError, Key, runtime_args, RuntimeArgs, U256, U512, URef};
-use contract_utils::{ContractContext, ContractStorage};
+use casper_types::account::AccountHash;
+use casper_types::bytesrepr::{Bytes, FromBytes};
+use contract_utils::{CesEvents, ContractContext, ContractStorage};
 use core::convert::TryInto;
 
-use crate::{data::{self}, event::MarketEvent, ITEM_STATUS_AVAILABLE, ITEM_STATUS_SOLD, Meta, NFTContractAddress, MarketItemId, TokenId};
-use crate::data::{Allowances, ItemAskingPriceData, ItemStatusData, ItemTokenIdData, NFTContractAddresses, NFTMarketItemIds, OwnedTokens, Owners};
+use crate::{
+    data::{self}, event::MarketEvent, CollectionStats, MarketInfo, MarketItem, MarketItemStatus,
+    Meta, NFTContractAddress, MarketItemId, SaleReceipt, SaleRecord, TokenId,
+};
+use crate::data::{
+    ActiveItems, Allowances, CollectionCapabilities, CollectionItems, CollectionStatsData,
+    CustodyItems, FloorPrices, MarketItems, NFTMarketItemIds, OwnedTokens, SellerHistory,
+    TokenSales,
+};
 
 #[repr(u16)]
 pub enum Error {
@@ -19,10 +28,30 @@ pub enum Error {
     MarketItemNotAvailable = 5,
     BalanceNotFound = 6,
     BalanceMismatch,
+    InvalidFeeConfig = 8,
+    IllegalStatusTransition = 9,
+    UnsupportedSchemaVersion = 10,
+    MarketNotApproved = 11,
+    TokenNotTransferable = 12,
+    ListingInvalid = 13,
+    TokenNotDelivered = 14,
 }
 
 const METHOD_BALANCE: &str = "balance";
 const ARG_PURSE: &str = "purse";
+const MAX_BASIS_POINTS: u32 = 10_000;
+/// Collection `meta` entries through which a CEP-47 collection asks for a royalty on sales.
+pub const ROYALTY_BASIS_POINTS: &str = "royalty_basis_points";
+pub const ROYALTY_RECIPIENT: &str = "royalty_recipient";
+/// Value CEP-47 `safe_transfer` expects back from `on_cep47_received`.
+pub const CEP47_RECEIVED_ACK: &str = "cep47_received";
+/// The collection implements CEP-47 operator approvals (`is_approval_for_all`).
+pub const CAPABILITY_OPERATOR_APPROVALS: u8 = 0b01;
+/// The collection can mint non-transferable tokens and reports them through `is_transferable`.
+pub const CAPABILITY_TRANSFERABILITY: u8 = 0b10;
+const ALL_CAPABILITIES: u8 = CAPABILITY_OPERATOR_APPROVALS | CAPABILITY_TRANSFERABILITY;
+/// Upper bound on the number of records a single paginated read returns.
+pub const MAX_PAGE_SIZE: u64 = 50;
 
 macro_rules! zip {
     ($x: expr) => ($x);
@@ -45,20 +74,47 @@ pub trait MarketContract<Storage: ContractStorage>: ContractContext<Storage> {
         data::set_symbol(symbol);
         data::set_meta(meta);
         data::set_total_supply(U256::zero());
-        Owners::init();
+        data::set_next_item_id(MarketItemId::zero());
+        data::set_schema_version(data::CURRENT_SCHEMA_VERSION);
+        data::set_fee_basis_points(0);
+        data::set_fee_recipient(self.get_caller());
+        MarketItems::init();
         OwnedTokens::init();
-        NFTContractAddresses::init();
         NFTMarketItemIds::init();
-        ItemAskingPriceData::init();
-        ItemStatusData::init();
-        ItemTokenIdData::init();
+        ActiveItems::init();
+        CollectionItems::init();
+        SellerHistory::init();
+        TokenSales::init();
+        CollectionStatsData::init();
+        FloorPrices::init();
+        CustodyItems::init();
+        CollectionCapabilities::init();
         Allowances::init();
-        let contract_hash = Key::Hash(self.self_addr().into_hash().unwrap());
-        let value_ref = storage::new_uref(contract_hash);
-        // TODO improve naming for this
+        CesEvents::init(MarketEvent::schemas());
+        // CEP-47 approvals have to name the market's package hash as spender; clients read it
+        // from here.
+        let package_hash = Key::Hash(self.self_addr().into_hash().unwrap());
+        let value_ref = storage::new_uref(package_hash);
         runtime::put_key("market_item_hash", Key::URef(value_ref));
     }
 
+    /// Brings storage written by an earlier contract version up to the current schema.
+    /// Dictionaries and named keys carry over to new versions, so only layout changes need work.
+    fn migrate(&mut self) -> Result<(), Error> {
+        // Version 1 is the first upgradeable layout. The baseline market kept no access URef, so
+        // it can't reach this entry point and has to be reinstalled instead.
+        if data::schema_version() != data::CURRENT_SCHEMA_VERSION {
+            return Err(Error::UnsupportedSchemaVersion);
+        }
+        // Republished on every upgrade so the schemas track the events this build emits.
+        CesEvents::init(MarketEvent::schemas());
+        Ok(())
+    }
+
+    fn schema_version(&self) -> u32 {
+        data::schema_version()
+    }
+
     fn name(&self) -> String {
         data::name()
     }
@@ -75,19 +131,132 @@ pub trait MarketContract<Storage: ContractStorage>: ContractContext<Storage> {
         data::total_supply()
     }
 
+    /// Takes tokens sent with CEP-47 `safe_transfer` into custody and lists them for `from`.
+    /// `data` is the bytesrepr encoded `Vec<U512>` of asking prices, one per token. The seller
+    /// gets a token back by cancelling its listing.
+    fn on_cep47_received(
+        &mut self,
+        _operator: Key,
+        from: Key,
+        token_ids: Vec<TokenId>,
+        data: Bytes,
+    ) -> Result<String, Error> {
+        let nft_contract_address = match data::caller_contract_hash() {
+            Some(contract_hash) => contract_hash,
+            None => return Err(Error::PermissionDenied),
+        };
+        // Sale proceeds are paid to the seller's account.
+        if from.into_account().is_none() {
+            return Err(Error::WrongArguments);
+        }
+        let item_asking_prices = match Vec::<U512>::from_bytes(&data) {
+            Ok((prices, rest)) if rest.is_empty() && prices.len() == token_ids.len() => prices,
+            _ => return Err(Error::WrongArguments),
+        };
+        let market = self.self_addr();
+        for token_id in &token_ids {
+            if self.token_owner(nft_contract_address, *token_id) != Some(market) {
+                return Err(Error::PermissionDenied);
+            }
+        }
+
+        let item_ids = self.next_item_ids(token_ids.len());
+        let custody_items = CustodyItems::instance();
+        for item_id in &item_ids {
+            custody_items.add(item_id);
+        }
+        self.insert_market_items(
+            from,
+            item_ids,
+            vec![nft_contract_address; token_ids.len()],
+            item_asking_prices,
+            token_ids,
+        );
+        Ok(String::from(CEP47_RECEIVED_ACK))
+    }
+
+    fn market_info(&self) -> MarketInfo {
+        let (fee_basis_points, fee_recipient) = self.fee_config();
+        MarketInfo {
+            contract_hash: data::contract_hash(),
+            contract_package_hash: data::contract_package_hash(),
+            fee_basis_points,
+            fee_recipient,
+            total_supply: self.total_supply(),
+            active_listings: self.active_listings_count(),
+        }
+    }
+
+    fn fee_config(&self) -> (u32, Key) {
+        (data::fee_basis_points(), data::fee_recipient())
+    }
+
+    fn set_fee_config(&mut self, fee_basis_points: u32, fee_recipient: Key) -> Result<(), Error> {
+        if fee_basis_points > MAX_BASIS_POINTS || fee_recipient.into_account().is_none() {
+            return Err(Error::InvalidFeeConfig);
+        }
+        data::set_fee_basis_points(fee_basis_points);
+        data::set_fee_recipient(fee_recipient);
+        self.emit(MarketEvent::SetFeeConfig {
+            fee_basis_points,
+            fee_recipient,
+        });
+        Ok(())
+    }
+
+    /// The optional CEP-47 extensions the market relies on for a collection. Collections an
+    /// admin never flagged are taken to be CEP-47 as built from this repository, with every
+    /// extension, so operator approvals are accepted for them by default.
+    fn collection_capabilities(&self, nft_contract_address: NFTContractAddress) -> u8 {
+        CollectionCapabilities::instance()
+            .get(&nft_contract_address)
+            .unwrap_or(ALL_CAPABILITIES)
+    }
+
+    /// Records which optional CEP-47 extensions a collection implements. Listing checks only
+    /// call the extension entry points of the flagged ones, so plain CEP-47 collections have to
+    /// be set to `0` before their tokens can be listed. Leaving out `CAPABILITY_TRANSFERABILITY`
+    /// declares that the collection mints no soulbound tokens.
+    fn set_collection_capabilities(
+        &mut self,
+        nft_contract_address: NFTContractAddress,
+        capabilities: u8,
+    ) -> Result<(), Error> {
+        if capabilities & !ALL_CAPABILITIES != 0 {
+            return Err(Error::WrongArguments);
+        }
+        CollectionCapabilities::instance().set(&nft_contract_address, capabilities);
+        Ok(())
+    }
+
     fn balance_of(&self, owner: Key) -> U256 {
         OwnedTokens::instance().get_balances(&owner)
     }
 
+    fn market_item(&self, item_id: MarketItemId) -> Option<MarketItem> {
+        MarketItems::instance().get(&item_id)
+    }
+
+    /// The item as buyers should see it: an open listing whose token no longer backs it reads
+    /// as `Invalid`, even before anyone calls `invalidate_market_item`.
+    fn listing(&self, item_id: MarketItemId) -> Option<MarketItem> {
+        self.market_item(item_id).map(|mut item| {
+            if !item.status.is_final() && !self.is_listing_backed(&item) {
+                item.status = MarketItemStatus::Invalid;
+            }
+            item
+        })
+    }
+
     fn owner_of(&self, item_id: MarketItemId) -> Option<Key> {
-        Owners::instance().get(&item_id)
+        self.market_item(item_id).map(|item| item.seller)
     }
 
     fn item_nft_contract_address(&self, item_id: MarketItemId) -> Option<NFTContractAddress> {
-        NFTContractAddresses::instance().get(&item_id)
+        self.market_item(item_id).map(|item| item.nft_contract_address)
     }
 
-    fn token_market_status(&self, item_token_id: U256) -> Option<String> {
+    fn token_market_status(&self, item_token_id: U256) -> Option<MarketItemStatus> {
         let market_item_ids = NFTMarketItemIds::instance().get(item_token_id).unwrap();
         self.item_status(*market_item_ids.last().unwrap())
     }
@@ -97,39 +266,317 @@ pub trait MarketContract<Storage: ContractStorage>: ContractContext<Storage> {
         item_id: MarketItemId,
         nft_contract_hash: NFTContractAddress,
     ) -> Result<(), Error> {
-        if self.owner_of(item_id).is_none() {
-            return Err(Error::MarketItemIdDoesntExist);
+        let mut item = match self.market_item(item_id) {
+            Some(item) => item,
+            None => return Err(Error::MarketItemIdDoesntExist),
         };
 
-        let nft_contract_addresses_dict = NFTContractAddresses::instance();
-        nft_contract_addresses_dict.set(&item_id, nft_contract_hash);
+        item.nft_contract_address = nft_contract_hash;
+        MarketItems::instance().set(&item_id, item);
 
         Ok(())
     }
 
     fn item_asking_price(&self, item_id: MarketItemId) -> Option<U512> {
-        ItemAskingPriceData::instance().get(&item_id)
+        self.market_item(item_id).map(|item| item.asking_price)
     }
 
-    fn item_token_id(&self, item_id: MarketItemId) -> Option<U256> {
-        ItemTokenIdData::instance().get(&item_id)
+    fn item_token_id(&self, item_id: MarketItemId) -> Option<TokenId> {
+        self.market_item(item_id).map(|item| item.token_id)
     }
 
-    fn item_status(&self, item_id: MarketItemId) -> Option<String> {
-        ItemStatusData::instance().get(&item_id)
+    fn item_status(&self, item_id: MarketItemId) -> Option<MarketItemStatus> {
+        self.listing(item_id).map(|item| item.status)
     }
 
-    fn set_item_status(&mut self, item_id: MarketItemId, value: String) -> Result<(), Error> {
-        if self.owner_of(item_id).is_none() {
-            return Err(Error::PermissionDenied);
+    /// Lets an admin move a listing through the status table. A listing is only `Sold` once
+    /// `process_market_sale` has delivered the token and paid for it.
+    fn set_item_status(
+        &mut self,
+        item_id: MarketItemId,
+        status: MarketItemStatus,
+    ) -> Result<(), Error> {
+        if status == MarketItemStatus::Sold {
+            return Err(Error::IllegalStatusTransition);
+        }
+        self.update_item_status(item_id, status)?;
+        self.emit(MarketEvent::SetItemStatus { item_id, status });
+        Ok(())
+    }
+
+    /// Moves a listing to `status` and keeps the listing indexes and custody in step. Callers
+    /// emit the event describing why the status changed.
+    fn update_item_status(
+        &mut self,
+        item_id: MarketItemId,
+        status: MarketItemStatus,
+    ) -> Result<(), Error> {
+        let mut item = match self.market_item(item_id) {
+            Some(item) => item,
+            None => return Err(Error::MarketItemIdDoesntExist),
         };
 
-        let item_status_dict = ItemStatusData::instance();
-        item_status_dict.set(&item_id, value);
+        if !item.status.can_transition_to(status) {
+            return Err(Error::IllegalStatusTransition);
+        }
+
+        // Keep the active listing indexes in step with every status change.
+        let was_active = item.status == MarketItemStatus::Available;
+        item.status = status;
+        MarketItems::instance().set(&item_id, item.clone());
+        if was_active {
+            self.close_listing(&item);
+        } else if status == MarketItemStatus::Available {
+            self.open_listing(&item);
+        }
+        let custody_items = CustodyItems::instance();
+        if status.is_final() && custody_items.contains(&item_id) {
+            // A sold token has gone to the buyer; closing a listing any other way hands the
+            // token back to the seller.
+            if status != MarketItemStatus::Sold && self.is_listing_backed(&item) {
+                self.transfer_token(&item, item.seller);
+            }
+            custody_items.remove(&item_id);
+        }
+        if status.is_final() {
+            SellerHistory::instance().push(&item.seller, &item_id);
+        }
 
         Ok(())
     }
 
+    fn open_listing(&mut self, item: &MarketItem) {
+        ActiveItems::instance().add(&item.item_id);
+        CollectionItems::instance().add(&item.nft_contract_address, &item.item_id);
+        OwnedTokens::instance().set_token(&item.seller, &item.item_id);
+
+        let floor_prices = FloorPrices::instance();
+        floor_prices.push(&item.nft_contract_address, &item.item_id, item.asking_price);
+
+        let stats_data = CollectionStatsData::instance();
+        let mut stats = stats_data.get(&item.nft_contract_address);
+        stats.active_listings += U256::one();
+        stats.floor_price = floor_prices
+            .floor(&item.nft_contract_address)
+            .map(|(price, _)| price);
+        stats_data.set(&item.nft_contract_address, stats);
+    }
+
+    fn close_listing(&mut self, item: &MarketItem) {
+        ActiveItems::instance().remove(&item.item_id);
+        CollectionItems::instance().remove(&item.nft_contract_address, &item.item_id);
+        OwnedTokens::instance().remove_token(&item.seller, &item.item_id);
+        let floor_prices = FloorPrices::instance();
+        floor_prices.remove(&item.nft_contract_address, &item.item_id);
+
+        let stats_data = CollectionStatsData::instance();
+        let mut stats = stats_data.get(&item.nft_contract_address);
+        stats.active_listings -= U256::one();
+        stats.floor_price = floor_prices
+            .floor(&item.nft_contract_address)
+            .map(|(price, _)| price);
+        stats_data.set(&item.nft_contract_address, stats);
+    }
+
+    /// The stored stats, with the floor price taken from listings whose token still backs them.
+    /// Past `MAX_PAGE_SIZE` unbacked listings below the floor, no floor is reported until they
+    /// are invalidated.
+    fn collection_stats(&self, nft_contract_address: NFTContractAddress) -> CollectionStats {
+        let mut stats = CollectionStatsData::instance().get(&nft_contract_address);
+        stats.floor_price = FloorPrices::instance()
+            .lowest(&nft_contract_address, MAX_PAGE_SIZE, |item_id| {
+                self.market_item(*item_id).map_or(false, |item| self.is_listing_backed(&item))
+            })
+            .map(|(price, _)| price);
+        stats
+    }
+
+    fn cancel_market_item(&mut self, item_id: MarketItemId) -> Result<(), Error> {
+        let seller = match self.owner_of(item_id) {
+            Some(seller) => seller,
+            None => return Err(Error::MarketItemIdDoesntExist),
+        };
+        if seller != self.get_caller() {
+            return Err(Error::PermissionDenied);
+        }
+
+        self.update_item_status(item_id, MarketItemStatus::Cancelled)?;
+        self.emit(MarketEvent::CancelItem { seller, item_id });
+        Ok(())
+    }
+
+    /// Closes a listing whose token no longer belongs to its seller, e.g. because the token was
+    /// burned. Anyone may call it, as it only ever removes listings that can't be sold.
+    fn invalidate_market_item(&mut self, item_id: MarketItemId) -> Result<(), Error> {
+        let item = match self.market_item(item_id) {
+            Some(item) => item,
+            None => return Err(Error::MarketItemIdDoesntExist),
+        };
+        if self.is_listing_backed(&item) {
+            return Err(Error::WrongArguments);
+        }
+
+        self.update_item_status(item_id, MarketItemStatus::Invalid)?;
+        self.emit(MarketEvent::InvalidateItem { item_id });
+        Ok(())
+    }
+
+    /// Whether the listed token still exists and belongs to the seller, or to the market for
+    /// tokens it holds in custody.
+    fn is_listing_backed(&self, item: &MarketItem) -> bool {
+        let holder = if CustodyItems::instance().contains(&item.item_id) {
+            self.self_addr()
+        } else {
+            item.seller
+        };
+        self.token_owner(item.nft_contract_address, item.token_id) == Some(holder)
+    }
+
+    fn token_owner(
+        &self,
+        nft_contract_address: NFTContractAddress,
+        token_id: TokenId,
+    ) -> Option<Key> {
+        runtime::call_contract(
+            nft_contract_address,
+            "owner_of",
+            runtime_args! {
+                "token_id" => token_id
+            },
+        )
+    }
+
+    /// Moves the listed token to `recipient`, out of the market's custody or from the seller.
+    fn transfer_token(&mut self, item: &MarketItem, recipient: Key) {
+        if CustodyItems::instance().contains(&item.item_id) {
+            let _: () = runtime::call_contract(
+                item.nft_contract_address,
+                "transfer",
+                runtime_args! {
+                    "recipient" => recipient,
+                    "token_ids" => vec![item.token_id]
+                },
+            );
+        } else {
+            let _: () = runtime::call_contract(
+                item.nft_contract_address,
+                "transfer_from",
+                runtime_args! {
+                    "sender" => item.seller,
+                    "recipient" => recipient,
+                    "token_ids" => vec![item.token_id]
+                },
+            );
+        }
+    }
+
+    fn seller_history_count(&self, owner: Key) -> U256 {
+        SellerHistory::instance().get_count(&owner)
+    }
+
+    fn seller_history(&self, owner: Key, offset: u64, limit: u64) -> Vec<MarketItem> {
+        let seller_history = SellerHistory::instance();
+        self.collect_page(seller_history.get_count(&owner), offset, limit, |index| {
+            seller_history.get_item_by_index(&owner, index)
+        })
+    }
+
+    fn last_sale_price(
+        &self,
+        nft_contract_address: NFTContractAddress,
+        token_id: TokenId,
+    ) -> Option<U512> {
+        let token_sales = TokenSales::instance();
+        let count = token_sales.get_count(&nft_contract_address, &token_id);
+        if count.is_zero() {
+            return None;
+        }
+        token_sales
+            .get_sale_by_index(&nft_contract_address, &token_id, &(count - 1))
+            .map(|sale| sale.price)
+    }
+
+    fn sale_history(
+        &self,
+        nft_contract_address: NFTContractAddress,
+        token_id: TokenId,
+        offset: u64,
+        limit: u64,
+    ) -> Vec<SaleRecord> {
+        let token_sales = TokenSales::instance();
+        let length = token_sales.get_count(&nft_contract_address, &token_id);
+        let start = U256::from(offset);
+        let end = length.min(start + U256::from(limit.min(MAX_PAGE_SIZE)));
+        let mut sales = Vec::new();
+        let mut index = start;
+        while index < end {
+            sales.push(
+                token_sales
+                    .get_sale_by_index(&nft_contract_address, &token_id, &index)
+                    .unwrap_or_revert(),
+            );
+            index += U256::one();
+        }
+        sales
+    }
+
+    fn active_listings_count(&self) -> U256 {
+        ActiveItems::instance().len()
+    }
+
+    fn active_listing_by_index(&self, index: U256) -> Option<MarketItemId> {
+        ActiveItems::instance().get_item_by_index(&index)
+    }
+
+    fn active_listings(&self, offset: u64, limit: u64) -> Vec<MarketItem> {
+        let active_items = ActiveItems::instance();
+        self.collect_page(active_items.len(), offset, limit, |index| {
+            active_items.get_item_by_index(index)
+        })
+    }
+
+    fn listings_by_seller(&self, owner: Key, offset: u64, limit: u64) -> Vec<MarketItem> {
+        let owned_tokens = OwnedTokens::instance();
+        self.collect_page(owned_tokens.get_balances(&owner), offset, limit, |index| {
+            owned_tokens.get_item_by_index(&owner, index)
+        })
+    }
+
+    fn listings_by_collection(
+        &self,
+        collection: NFTContractAddress,
+        offset: u64,
+        limit: u64,
+    ) -> Vec<MarketItem> {
+        let collection_items = CollectionItems::instance();
+        let length = collection_items.get_count(&collection);
+        self.collect_page(length, offset, limit, |index| {
+            collection_items.get_item_by_index(&collection, index)
+        })
+    }
+
+    fn collect_page<F>(
+        &self,
+        length: U256,
+        offset: u64,
+        limit: u64,
+        item_at: F,
+    ) -> Vec<MarketItem>
+    where
+        F: Fn(&U256) -> Option<MarketItemId>,
+    {
+        let start = U256::from(offset);
+        let end = length.min(start + U256::from(limit.min(MAX_PAGE_SIZE)));
+        let mut items = Vec::new();
+        let mut index = start;
+        while index < end {
+            let item_id = item_at(&index).unwrap_or_revert();
+            items.push(self.listing(item_id).unwrap_or_revert());
+            index += U256::one();
+        }
+        items
+    }
+
     fn get_item_by_index(&self, owner: Key, index: U256) -> Option<MarketItemId> {
         OwnedTokens::instance().get_item_by_index(&owner, &index)
     }
@@ -144,6 +591,38 @@ pub trait MarketContract<Storage: ContractStorage>: ContractContext<Storage> {
     }
 
     fn create_market_item(
+        &mut self,
+        recipient: Key,
+        nft_contract_addresses: Vec<NFTContractAddress>,
+        item_asking_prices: Vec<U512>,
+        item_token_ids: Vec<U256>,
+    ) -> Result<Vec<MarketItemId>, Error> {
+        let item_ids = self.next_item_ids(nft_contract_addresses.len());
+        self.create_market_item_with_ids(
+            recipient,
+            item_ids,
+            nft_contract_addresses,
+            item_asking_prices,
+            item_token_ids,
+        )
+    }
+
+    /// Takes `count` ids from the item id counter. Items created through
+    /// `create_market_item_with_ids` may already occupy the counter's range, so skip them.
+    fn next_item_ids(&mut self, count: usize) -> Vec<MarketItemId> {
+        let mut next_item_id = data::next_item_id();
+        let mut item_ids = Vec::with_capacity(count);
+        while item_ids.len() < count {
+            if self.owner_of(next_item_id).is_none() {
+                item_ids.push(next_item_id);
+            }
+            next_item_id += MarketItemId::one();
+        }
+        data::set_next_item_id(next_item_id);
+        item_ids
+    }
+
+    fn create_market_item_with_ids(
         &mut self,
         recipient: Key,
         item_ids: Vec<MarketItemId>,
@@ -151,6 +630,10 @@ pub trait MarketContract<Storage: ContractStorage>: ContractContext<Storage> {
         item_asking_prices: Vec<U512>,
         item_token_ids: Vec<U256>,
     ) -> Result<Vec<MarketItemId>, Error> {
+        // Approvals only let the market move tokens, so sellers must list their own.
+        if recipient != self.get_caller() {
+            return Err(Error::PermissionDenied);
+        }
         if item_ids.len() != nft_contract_addresses.len() {
             return Err(Error::WrongArguments);
         };
@@ -166,30 +649,50 @@ pub trait MarketContract<Storage: ContractStorage>: ContractContext<Storage> {
                 return Err(Error::MarketItemIdAlreadyExists);
             }
         }
-        // TODO check is owner
+        let zipped = zip!(&nft_contract_addresses, &item_token_ids);
+        for (nft_contract_address, item_token_id) in zipped {
+            self.assert_listable(recipient, *nft_contract_address, *item_token_id)?;
+        }
 
-        // TODO check this contract approved
+        Ok(self.insert_market_items(
+            recipient,
+            item_ids,
+            nft_contract_addresses,
+            item_asking_prices,
+            item_token_ids,
+        ))
+    }
 
-        let owners_dict = Owners::instance();
-        let owned_tokens_dict = OwnedTokens::instance();
+    /// Stores and opens already validated listings.
+    fn insert_market_items(
+        &mut self,
+        recipient: Key,
+        item_ids: Vec<MarketItemId>,
+        nft_contract_addresses: Vec<NFTContractAddress>,
+        item_asking_prices: Vec<U512>,
+        item_token_ids: Vec<U256>,
+    ) -> Vec<MarketItemId> {
+        let market_items_dict = MarketItems::instance();
         let nft_market_item_ids_dict = NFTMarketItemIds::instance();
-        let nft_contract_addresses_dict = NFTContractAddresses::instance();
-        let item_asking_prices_dict = ItemAskingPriceData::instance();
-        let item_token_ids_dict = ItemTokenIdData::instance();
-        let item_status_dict = ItemStatusData::instance();
-
-        let zipped = zip!(&item_ids, &nft_contract_addresses, &item_token_ids);
-        for (item_id, (nft_contract_address, item_token_id)) in zipped {
-            nft_contract_addresses_dict.set(item_id, nft_contract_address.clone());
-            owners_dict.set(item_id, recipient);
-            owned_tokens_dict.set_token(&recipient, item_id);
-            item_status_dict.set(item_id, String::from(ITEM_STATUS_AVAILABLE));
-            item_token_ids_dict.set(item_id, *item_token_id);
-            nft_market_item_ids_dict.set(item_token_id, *item_id);
-        }
 
-        for (item_id, item_asking_price) in item_ids.iter().zip(item_asking_prices) {
-            item_asking_prices_dict.set(item_id, item_asking_price);
+        let zipped = zip!(
+            &item_ids,
+            &nft_contract_addresses,
+            &item_token_ids,
+            &item_asking_prices
+        );
+        for (item_id, (nft_contract_address, (item_token_id, item_asking_price))) in zipped {
+            let item = MarketItem {
+                item_id: *item_id,
+                seller: recipient,
+                nft_contract_address: *nft_contract_address,
+                token_id: *item_token_id,
+                asking_price: *item_asking_price,
+                status: MarketItemStatus::Available,
+            };
+            market_items_dict.set(item_id, item.clone());
+            nft_market_item_ids_dict.set(item_token_id, *item_id);
+            self.open_listing(&item);
         }
 
         let created_items_count: U256 = From::<u64>::from(item_ids.len().try_into().unwrap());
@@ -202,7 +705,68 @@ pub trait MarketContract<Storage: ContractStorage>: ContractContext<Storage> {
             recipient,
             item_ids: item_ids.clone(),
         });
-        Ok(item_ids)
+        item_ids
+    }
+
+    /// Checks that `seller` owns a transferable token and has approved the market to move it,
+    /// either for this token alone or, on collections with operator approvals, as an operator
+    /// over all of the seller's tokens.
+    ///
+    /// Unflagged collections have the transferability capability, so soulbound tokens are
+    /// refused here with `TokenNotTransferable`. A collection flagged without it is taken to mint
+    /// none; should it still hold one, CEP-47 refuses to approve it and listing fails with
+    /// `MarketNotApproved` instead.
+    fn assert_listable(
+        &self,
+        seller: Key,
+        nft_contract_address: NFTContractAddress,
+        token_id: TokenId,
+    ) -> Result<(), Error> {
+        if self.token_owner(nft_contract_address, token_id) != Some(seller) {
+            return Err(Error::PermissionDenied);
+        }
+
+        let capabilities = self.collection_capabilities(nft_contract_address);
+        if capabilities & CAPABILITY_TRANSFERABILITY != 0 {
+            let is_transferable: bool = runtime::call_contract(
+                nft_contract_address,
+                "is_transferable",
+                runtime_args! {
+                    "token_id" => token_id
+                },
+            );
+            if !is_transferable {
+                return Err(Error::TokenNotTransferable);
+            }
+        }
+
+        let market = self.self_addr();
+        let approved: Option<Key> = runtime::call_contract(
+            nft_contract_address,
+            "get_approved",
+            runtime_args! {
+                "owner" => seller,
+                "token_id" => token_id
+            },
+        );
+        if approved == Some(market) {
+            return Ok(());
+        }
+        if capabilities & CAPABILITY_OPERATOR_APPROVALS == 0 {
+            return Err(Error::MarketNotApproved);
+        }
+        let is_operator: bool = runtime::call_contract(
+            nft_contract_address,
+            "is_approval_for_all",
+            runtime_args! {
+                "owner" => seller,
+                "operator" => market
+            },
+        );
+        if !is_operator {
+            return Err(Error::MarketNotApproved);
+        }
+        Ok(())
     }
 
     fn process_market_sale(
@@ -210,14 +774,22 @@ pub trait MarketContract<Storage: ContractStorage>: ContractContext<Storage> {
         recipient: Key,
         item_id: MarketItemId,
         market_offer_purse: URef,
-    ) -> Result<(), Error> {
+    ) -> Result<SaleReceipt, Error> {
+        let item = match self.market_item(item_id) {
+            Some(item) => item,
+            None => return Err(Error::MarketItemIdDoesntExist),
+        };
+
         // Check item status available
-        if self.item_status(item_id).unwrap_or_revert() != *ITEM_STATUS_AVAILABLE {
+        if item.status != MarketItemStatus::Available {
             return Err(Error::MarketItemNotAvailable);
         };
+        if !self.is_listing_backed(&item) {
+            return Err(Error::ListingInvalid);
+        }
 
         // Manage payment
-        let asking_price = self.item_asking_price(item_id).unwrap_or_revert();
+        let asking_price = item.asking_price;
         let mint = system::get_mint();
 
         let balance: Option<U512> = runtime::call_contract(
@@ -233,40 +805,103 @@ pub trait MarketContract<Storage: ContractStorage>: ContractContext<Storage> {
             _ => runtime::revert(ApiError::User(Error::BalanceMismatch as u16)),
         }
 
-        let value_ref = storage::new_uref(balance);
-        runtime::put_key("balance", Key::URef(value_ref));
+        let nft_contract_hash = item.nft_contract_address;
+        let token_id = item.token_id;
+        let owner = item.seller;
 
-        let value_ref = storage::new_uref(asking_price);
-        runtime::put_key("asking_price", Key::URef(value_ref));
+        self.transfer_token(&item, recipient);
+        if self.token_owner(nft_contract_hash, token_id) != Some(recipient) {
+            return Err(Error::TokenNotDelivered);
+        }
 
-        let nft_contract_hash = self.item_nft_contract_address(item_id).unwrap();
-        let token_id = self.item_token_id(item_id).unwrap();
-        let owner = self.owner_of(item_id).unwrap_or_revert();
+        let (fee_basis_points, fee_recipient) = self.fee_config();
+        let fee = asking_price * U512::from(fee_basis_points) / U512::from(MAX_BASIS_POINTS);
+        if !fee.is_zero() {
+            system::transfer_from_purse_to_account(
+                market_offer_purse,
+                fee_recipient.into_account().unwrap_or_revert(),
+                fee,
+                None,
+            )
+                .unwrap_or_revert();
+        }
 
-        let _: () = runtime::call_contract(
-            nft_contract_hash,
-            "transfer_from",
-            runtime_args! {
-                "sender" => owner,
-                "recipient" => recipient,
-                "token_ids" => vec![token_id]
-            },
-        );
-        // TODO check ownership transferred to buyer
+        // Collections set their royalty in their own metadata, so it is capped to the share of
+        // the price the market fee leaves.
+        let royalty = match self.royalty_config(nft_contract_hash) {
+            Some((royalty_basis_points, royalty_recipient)) => {
+                let royalty_basis_points =
+                    royalty_basis_points.min(MAX_BASIS_POINTS - fee_basis_points);
+                let royalty = asking_price * U512::from(royalty_basis_points)
+                    / U512::from(MAX_BASIS_POINTS);
+                if !royalty.is_zero() {
+                    system::transfer_from_purse_to_account(
+                        market_offer_purse,
+                        royalty_recipient,
+                        royalty,
+                        None,
+                    )
+                        .unwrap_or_revert();
+                }
+                royalty
+            }
+            None => U512::zero(),
+        };
 
         // transfer money to seller/owner
-        system::transfer_from_purse_to_account(
-            market_offer_purse,
-            owner.into_account().unwrap_or_revert(),
-            asking_price,
-            None,
-        )
-            .unwrap_or_revert();
+        let proceeds = asking_price - fee - royalty;
+        if !proceeds.is_zero() {
+            system::transfer_from_purse_to_account(
+                market_offer_purse,
+                owner.into_account().unwrap_or_revert(),
+                proceeds,
+                None,
+            )
+                .unwrap_or_revert();
+        }
 
-        self.set_item_status(item_id, ITEM_STATUS_SOLD.to_string())
-            .unwrap_or_revert();
+        self.update_item_status(item_id, MarketItemStatus::Sold).unwrap_or_revert();
+        let stats_data = CollectionStatsData::instance();
+        let mut stats = stats_data.get(&nft_contract_hash);
+        stats.volume += asking_price;
+        stats.sales_count += U256::one();
+        stats_data.set(&nft_contract_hash, stats);
+        TokenSales::instance().push(
+            &nft_contract_hash,
+            &token_id,
+            SaleRecord {
+                item_id,
+                seller: owner,
+                buyer: recipient,
+                price: asking_price,
+                timestamp: runtime::get_blocktime().into(),
+            },
+        );
         self.emit(MarketEvent::SoldItem { recipient, item_id });
-        Ok(())
+        Ok(SaleReceipt {
+            item_id,
+            seller: owner,
+            buyer: recipient,
+            price: asking_price,
+            fee,
+            royalty,
+        })
+    }
+
+    /// Royalty the collection asks on sales, as basis points of the price and the account that
+    /// receives it. Collections whose `meta` lacks either entry, or holds a malformed one, ask
+    /// for none.
+    fn royalty_config(
+        &self,
+        nft_contract_address: NFTContractAddress,
+    ) -> Option<(u32, AccountHash)> {
+        let meta: Meta = runtime::call_contract(nft_contract_address, "meta", runtime_args! {});
+        let basis_points = meta.get(ROYALTY_BASIS_POINTS)?.parse::<u32>().ok()?;
+        let recipient = AccountHash::from_formatted_str(meta.get(ROYALTY_RECIPIENT)?).ok()?;
+        if basis_points > MAX_BASIS_POINTS {
+            return None;
+        }
+        Some((basis_points, recipient))
     }
 
     fn emit(&mut self, event: MarketEvent) {
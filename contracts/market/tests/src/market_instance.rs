@@ -5,8 +5,9 @@ use blake2::{
 use casper_engine_test_support::WasmTestBuilder;
 use casper_execution_engine::storage::global_state::in_memory::InMemoryGlobalState;
 use casper_types::{
-    account::AccountHash, bytesrepr::ToBytes, CLTyped, ContractHash, Key, runtime_args,
-    RuntimeArgs, U256, U512,
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped, ContractHash, Key, runtime_args, RuntimeArgs, U256, U512,
 };
 use std::collections::BTreeMap;
 use std::fmt::Debug;
@@ -18,12 +19,8 @@ pub type Meta = BTreeMap<String, String>;
 
 const BALANCES_DICT: &str = "item_balances";
 pub const ALLOWANCES_DICT: &str = "item_allowances";
-const NFT_CONTRACT_ADDRESSES: &str = "nft_contract_addresses";
-const ITEM_ASKING_PRICE_DATA: &str = "item_asking_prices";
-const ITEM_TOKEN_ID_DATA: &str = "item_token_ids";
-const ITEM_STATUS_DATA: &str = "item_statuses";
+pub const MARKET_ITEMS_DICT: &str = "market_items";
 const ITEM_PURSE_DATA: &str = "item_purses";
-const OWNERS_DICT: &str = "item_owners";
 const OWNED_ITEMS_BY_INDEX_DICT: &str = "owned_items_by_index";
 const OWNED_INDEXES_BY_ITEM_DICT: &str = "owned_indexes_by_item";
 pub const NFT_CONTRACT_ADDRESS: &str = "nft_contract_hash";
@@ -32,6 +29,145 @@ pub const MARKET_NAME_KEY: &str = "market_name";
 pub const SYMBOL: &str = "market_symbol";
 pub const META: &str = "market_meta";
 
+/// Mirror of `market::MarketItemStatus`, decoded from its `u8` tag.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MarketItemStatus {
+    Available = 0,
+    Sold = 1,
+    Cancelled = 2,
+    Expired = 3,
+    Invalid = 4,
+    InAuction = 5,
+}
+
+impl CLTyped for MarketItemStatus {
+    fn cl_type() -> CLType {
+        CLType::U8
+    }
+}
+
+impl FromBytes for MarketItemStatus {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, bytes) = u8::from_bytes(bytes)?;
+        let value = match tag {
+            0 => MarketItemStatus::Available,
+            1 => MarketItemStatus::Sold,
+            2 => MarketItemStatus::Cancelled,
+            3 => MarketItemStatus::Expired,
+            4 => MarketItemStatus::Invalid,
+            5 => MarketItemStatus::InAuction,
+            _ => return Err(bytesrepr::Error::Formatting),
+        };
+        Ok((value, bytes))
+    }
+}
+
+/// Mirror of `market::MarketItem` as stored in the `market_items` dictionary.
+#[derive(Debug, PartialEq)]
+pub struct MarketItem {
+    pub item_id: TokenId,
+    pub seller: Key,
+    pub nft_contract_address: NFTContractAddress,
+    pub token_id: TokenId,
+    pub asking_price: U512,
+    pub status: MarketItemStatus,
+}
+
+impl CLTyped for MarketItem {
+    fn cl_type() -> CLType {
+        <(
+            (TokenId, Key, NFTContractAddress),
+            (TokenId, U512, MarketItemStatus),
+        )>::cl_type()
+    }
+}
+
+impl FromBytes for MarketItem {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (item_id, bytes) = FromBytes::from_bytes(bytes)?;
+        let (seller, bytes) = FromBytes::from_bytes(bytes)?;
+        let (nft_contract_address, bytes) = FromBytes::from_bytes(bytes)?;
+        let (token_id, bytes) = FromBytes::from_bytes(bytes)?;
+        let (asking_price, bytes) = FromBytes::from_bytes(bytes)?;
+        let (status, bytes) = FromBytes::from_bytes(bytes)?;
+        let value = MarketItem {
+            item_id,
+            seller,
+            nft_contract_address,
+            token_id,
+            asking_price,
+            status,
+        };
+        Ok((value, bytes))
+    }
+}
+
+/// Mirror of `market::SaleRecord` as stored in the `token_sales_by_index` dictionary.
+#[derive(Debug, PartialEq)]
+pub struct SaleRecord {
+    pub item_id: TokenId,
+    pub seller: Key,
+    pub buyer: Key,
+    pub price: U512,
+    pub timestamp: u64,
+}
+
+impl CLTyped for SaleRecord {
+    fn cl_type() -> CLType {
+        <((TokenId, Key, Key), (U512, u64))>::cl_type()
+    }
+}
+
+impl FromBytes for SaleRecord {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (item_id, bytes) = FromBytes::from_bytes(bytes)?;
+        let (seller, bytes) = FromBytes::from_bytes(bytes)?;
+        let (buyer, bytes) = FromBytes::from_bytes(bytes)?;
+        let (price, bytes) = FromBytes::from_bytes(bytes)?;
+        let (timestamp, bytes) = FromBytes::from_bytes(bytes)?;
+        let value = SaleRecord {
+            item_id,
+            seller,
+            buyer,
+            price,
+            timestamp,
+        };
+        Ok((value, bytes))
+    }
+}
+
+/// Mirror of `market::CollectionStats` as stored in the `collection_stats` dictionary.
+#[derive(Debug, Default, PartialEq)]
+pub struct CollectionStats {
+    pub volume: U512,
+    pub sales_count: U256,
+    pub active_listings: U256,
+    pub floor_price: Option<U512>,
+}
+
+impl CLTyped for CollectionStats {
+    fn cl_type() -> CLType {
+        <((U512, U256), (U256, Option<U512>))>::cl_type()
+    }
+}
+
+impl FromBytes for CollectionStats {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (volume, bytes) = FromBytes::from_bytes(bytes)?;
+        let (sales_count, bytes) = FromBytes::from_bytes(bytes)?;
+        let (active_listings, bytes) = FromBytes::from_bytes(bytes)?;
+        let (floor_price, bytes) = FromBytes::from_bytes(bytes)?;
+        let value = CollectionStats {
+            volume,
+            sales_count,
+            active_listings,
+            floor_price,
+        };
+        Ok((value, bytes))
+    }
+}
+
 pub struct MarketContractInstance(TestContract);
 
 impl MarketContractInstance {
@@ -72,7 +208,6 @@ impl MarketContractInstance {
         &self,
         sender: AccountHash,
         recipient: T,
-        item_id: TokenId,
         item_nft_contract_address: NFTContractAddress,
         item_asking_price: U512,
         item_token_id: U256,
@@ -80,6 +215,27 @@ impl MarketContractInstance {
         self.0.call_contract(
             sender,
             "create_market_item",
+            runtime_args! {
+                "recipient" => recipient.into(),
+                "item_nft_contract_addresses" => vec![item_nft_contract_address],
+                "item_asking_prices" => vec![item_asking_price],
+                "item_token_ids" => vec![item_token_id]
+            },
+        )
+    }
+
+    pub fn create_market_item_with_id<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        recipient: T,
+        item_id: TokenId,
+        item_nft_contract_address: NFTContractAddress,
+        item_asking_price: U512,
+        item_token_id: U256,
+    ) -> WasmTestBuilder<InMemoryGlobalState> {
+        self.0.call_contract(
+            sender,
+            "create_market_item_with_ids",
             runtime_args! {
                 "recipient" => recipient.into(),
                 "item_ids" => vec![item_id],
@@ -101,7 +257,7 @@ impl MarketContractInstance {
     ) -> WasmTestBuilder<InMemoryGlobalState> {
         self.0.call_contract(
             sender,
-            "create_market_item",
+            "create_market_item_with_ids",
             runtime_args! {
                 "recipient" => recipient.into(),
                 "item_ids" => item_ids,
@@ -153,28 +309,30 @@ impl MarketContractInstance {
             .unwrap_or_default()
     }
 
+    pub fn market_item(&self, item_id: TokenId) -> Option<MarketItem> {
+        self.0
+            .query_dictionary(MARKET_ITEMS_DICT, item_id.to_string())
+    }
+
     pub fn owner_of(&self, item_id: TokenId) -> Option<Key> {
-        self.0.query_dictionary(OWNERS_DICT, item_id.to_string())
+        self.market_item(item_id).map(|item| item.seller)
     }
 
     pub fn item_nft_contract_address(&self, item_id: TokenId) -> Option<NFTContractAddress> {
-        self.0
-            .query_dictionary(NFT_CONTRACT_ADDRESSES, item_id.to_string())
+        self.market_item(item_id)
+            .map(|item| item.nft_contract_address)
     }
 
     pub fn item_asking_price(&self, item_id: TokenId) -> Option<U512> {
-        self.0
-            .query_dictionary(ITEM_ASKING_PRICE_DATA, item_id.to_string())
+        self.market_item(item_id).map(|item| item.asking_price)
     }
 
     pub fn item_token_id(&self, item_id: TokenId) -> Option<U256> {
-        self.0
-            .query_dictionary(ITEM_TOKEN_ID_DATA, item_id.to_string())
+        self.market_item(item_id).map(|item| item.token_id)
     }
 
-    pub fn item_status(&self, item_id: TokenId) -> Option<String> {
-        self.0
-            .query_dictionary(ITEM_STATUS_DATA, item_id.to_string())
+    pub fn item_status(&self, item_id: TokenId) -> Option<MarketItemStatus> {
+        self.market_item(item_id).map(|item| item.status)
     }
 
     pub fn name(&self) -> String {
@@ -16,7 +16,7 @@ use casper_types::{
     U256, U512, URef,
 };
 use casper_types::account::blake2b;
-use casper_types::bytesrepr::{FromBytes, ToBytes};
+use casper_types::bytesrepr::{Bytes, FromBytes, ToBytes};
 use casper_types::CLType::ByteArray;
 use casper_types::KeyTag::Account;
 use cep47_tests::cep47_instance::CEP47Instance;
@@ -27,7 +27,10 @@ use std::hash::Hash;
 use std::path::PathBuf;
 use test_env::TestEnv;
 
-use crate::market_instance::{MARKET_NAME_KEY, MarketContractInstance, Meta, TokenId};
+use crate::market_instance::{
+    key_to_str, CollectionStats, MARKET_ITEMS_DICT, MARKET_NAME_KEY, MarketContractInstance, MarketItem, MarketItemStatus, Meta,
+    SaleRecord, TokenId,
+};
 use crate::market_tests::meta::contract_meta;
 
 const CEP47_NAME: &str = "Dragon NFT";
@@ -42,12 +45,22 @@ const MARKET_CONTRACT_NAME: &str = "market";
 const MARKET_CONTRACT_HASH_KEY: &str = "market_contract_hash";
 const MARKET_CONTRACT_PACKAGE_HASH_KEY: &str = "market_contract_hash_wrapped";
 const SYMBOL: &str = "DGNFT";
-pub const ITEM_STATUS_AVAILABLE: &str = "available";
-pub const ITEM_STATUS_CANCELLED: &str = "cancelled";
-pub const ITEM_STATUS_SOLD: &str = "sold";
 const MY_ACCOUNT: [u8; 32] = [7u8; 32];
 const MARKET_WASM: &str = "market.wasm";
 const CEP47_WASM: &str = "cep47-token.wasm";
+/// Session code that calls a market entry point and stores what it returns.
+const MARKET_QUERY_WASM: &str = "market-query.wasm";
+/// Longest page the market's paginated entry points return.
+const MAX_PAGE_SIZE: u64 = 50;
+/// The plain CEP-47 contract in `utils/cep47`, without operator approvals or non-transferable
+/// tokens.
+const PLAIN_CEP47_WASM: &str = "cep47-plain-token.wasm";
+/// The collection implements CEP-47 operator approvals.
+const CAPABILITY_OPERATOR_APPROVALS: u8 = 0b01;
+/// The collection reports non-transferable tokens through `is_transferable`.
+const CAPABILITY_TRANSFERABILITY: u8 = 0b10;
+/// Both optional CEP-47 extensions the market knows about.
+const ALL_CAPABILITIES: u8 = 0b11;
 
 mod meta {
     use super::{BTreeMap, Meta};
@@ -217,6 +230,8 @@ fn setup() -> (InMemoryWasmTestBuilder, TestFixture, Vec<TestAccount>) {
             "name" => CEP47_NAME,
             "symbol" => SYMBOL,
             "meta" => meta::contract_meta(),
+            "max_supply" => Option::<U256>::None,
+            "meta_schema" => Option::<BTreeMap<String, (u32, String)>>::None,
             "contract_name" => CEP47_CONTRACT_NAME,
             },
         )
@@ -300,6 +315,80 @@ fn setup() -> (InMemoryWasmTestBuilder, TestFixture, Vec<TestAccount>) {
     (test_builder, test_context, accounts)
 }
 
+fn upgrade_market(builder: &mut InMemoryWasmTestBuilder, test_context: TestFixture) -> TestFixture {
+    let exec_request = ExecuteRequestBuilder::standard(
+        test_context.owner.account_hash,
+        MARKET_WASM,
+        runtime_args! {
+            "contract_name" => MARKET_CONTRACT_NAME,
+        },
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let account = builder
+        .query(None, Key::Account(test_context.owner.account_hash), &[])
+        .expect("should query account")
+        .as_account()
+        .cloned()
+        .expect("should be account");
+    let market_contract_hash = *account
+        .named_keys()
+        .get(MARKET_CONTRACT_HASH_KEY)
+        .expect("should have market contract");
+
+    TestFixture {
+        market_contract_hash,
+        ..test_context
+    }
+}
+
+/// Installs another collection from `wasm` and returns a fixture that targets it instead of the
+/// default one.
+fn install_collection(
+    builder: &mut InMemoryWasmTestBuilder,
+    test_context: TestFixture,
+    wasm: &str,
+    contract_name: &str,
+    collection_meta: Meta,
+) -> TestFixture {
+    let exec_request = ExecuteRequestBuilder::standard(
+        test_context.owner.account_hash,
+        wasm,
+        runtime_args! {
+            "name" => CEP47_NAME,
+            "symbol" => SYMBOL,
+            "meta" => collection_meta,
+            "max_supply" => Option::<U256>::None,
+            "meta_schema" => Option::<BTreeMap<String, (u32, String)>>::None,
+            "contract_name" => contract_name,
+        },
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let account = builder
+        .query(None, Key::Account(test_context.owner.account_hash), &[])
+        .expect("should query account")
+        .as_account()
+        .cloned()
+        .expect("should be account");
+    let cep47_contract_hash = *account
+        .named_keys()
+        .get(&format!("{}_contract_hash", contract_name))
+        .expect("should have cep47 contract");
+    let cep47_contract_package_hash = *account
+        .named_keys()
+        .get(&format!("{}_contract_hash_wrapped", contract_name))
+        .expect("should have cep47 contract");
+
+    TestFixture {
+        cep47_contract_hash,
+        cep47_contract_package_hash,
+        ..test_context
+    }
+}
+
 fn nft_mint(
     builder: &mut InMemoryWasmTestBuilder,
     test_context: &TestFixture,
@@ -343,7 +432,7 @@ fn create_market_item(
     test_context: &TestFixture,
     sender: AccountHash,
     recipient: Key,
-    item_ids: Vec<TokenId>,
+    item_token_ids: Vec<TokenId>,
     asking_prices: Vec<U512>,
 ) {
     let method: &str = "create_market_item";
@@ -351,6 +440,47 @@ fn create_market_item(
         hash: ContractHash::from(test_context.market_contract_hash.into_hash().unwrap()),
         method: method.to_string(),
     };
+    let nft_contract_address =
+        ContractHash::from(test_context.cep47_contract_hash.into_hash().unwrap());
+    let args = runtime_args! {
+                "recipient" => recipient,
+                "item_nft_contract_addresses" => vec![nft_contract_address; item_token_ids.len()],
+                "item_asking_prices" => asking_prices,
+                "item_token_ids" => item_token_ids,
+    };
+    let mut deploy_builder = DeployItemBuilder::new()
+        .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
+        .with_address(sender)
+        .with_authorization_keys(&[sender]);
+    deploy_builder = match source {
+        DeploySource::Code(path) => deploy_builder.with_session_code(path, args),
+        DeploySource::ByHash { hash, method } => {
+            // let contract_hash = ContractHash::from(*hash);
+            deploy_builder.with_stored_session_hash(hash, &*method, args)
+        }
+    };
+
+    let mut execute_request_builder =
+        ExecuteRequestBuilder::from_deploy_item(deploy_builder.build());
+    builder
+        .exec(execute_request_builder.build())
+        .expect_success()
+        .commit();
+}
+
+fn create_market_item_with_ids(
+    builder: &mut InMemoryWasmTestBuilder,
+    test_context: &TestFixture,
+    sender: AccountHash,
+    recipient: Key,
+    item_ids: Vec<TokenId>,
+    asking_prices: Vec<U512>,
+) {
+    let method: &str = "create_market_item_with_ids";
+    let source = DeploySource::ByHash {
+        hash: ContractHash::from(test_context.market_contract_hash.into_hash().unwrap()),
+        method: method.to_string(),
+    };
     let args = runtime_args! {
                 "recipient" => recipient,
                 "item_ids" => item_ids,
@@ -414,16 +544,52 @@ fn process_market_sale(
         .commit();
 }
 
-fn owner_of(
+/// Approves the market's package hash as spender of `token_ids`, as listing requires.
+fn approve_market(
     builder: &mut InMemoryWasmTestBuilder,
     test_context: &TestFixture,
-    token_id: TokenId,
-) -> Option<Key> {
+    seller: AccountHash,
+    token_ids: Vec<TokenId>,
+) {
+    let spender = market_item_hash(builder, test_context);
+    approve(builder, test_context, seller, spender, token_ids);
+}
+
+fn mint_and_list(
+    builder: &mut InMemoryWasmTestBuilder,
+    test_context: &TestFixture,
+    seller: AccountHash,
+    asking_price: U512,
+) {
+    nft_mint(
+        builder,
+        test_context,
+        test_context.owner.account_hash,
+        seller,
+        vec![TokenId::zero()],
+        vec![meta::red_dragon()],
+    );
+    approve_market(builder, test_context, seller, vec![TokenId::zero()]);
+    create_market_item(
+        builder,
+        test_context,
+        seller,
+        Key::Account(seller),
+        vec![TokenId::zero()],
+        vec![asking_price],
+    );
+}
+
+fn market_item(
+    builder: &mut InMemoryWasmTestBuilder,
+    test_context: &TestFixture,
+    item_id: TokenId,
+) -> Option<MarketItem> {
     match query_dictionary_item(
         builder,
-        test_context.cep47_contract_hash,
-        "owners",
-        TokenId::zero().to_string(),
+        test_context.market_contract_hash,
+        MARKET_ITEMS_DICT,
+        item_id.to_string(),
     ) {
         Ok(value) => value
             .as_cl_value()
@@ -438,17 +604,119 @@ fn owner_of(
     }
 }
 
-fn get_approved(
+fn active_items_count(builder: &mut InMemoryWasmTestBuilder, test_context: &TestFixture) -> U256 {
+    query(
+        builder,
+        Key::Account(test_context.owner.account_hash),
+        &[
+            MARKET_CONTRACT_HASH_KEY.to_string(),
+            "active_items_count".to_string(),
+        ],
+    )
+}
+
+fn active_item_by_index(
+    builder: &mut InMemoryWasmTestBuilder,
+    test_context: &TestFixture,
+    index: U256,
+) -> Option<TokenId> {
+    query_market_dictionary(builder, test_context, "active_items_by_index", index.to_string())
+}
+
+fn collection_items_count(
+    builder: &mut InMemoryWasmTestBuilder,
+    test_context: &TestFixture,
+) -> U256 {
+    query_market_dictionary(
+        builder,
+        test_context,
+        "collection_item_counts",
+        hex::encode(test_context.cep47_contract_hash.into_hash().unwrap()),
+    )
+    .unwrap_or_default()
+}
+
+fn seller_listings_count(
+    builder: &mut InMemoryWasmTestBuilder,
+    test_context: &TestFixture,
+    seller: AccountHash,
+) -> U256 {
+    query_market_dictionary(
+        builder,
+        test_context,
+        "item_balances",
+        key_to_str(&Key::Account(seller)),
+    )
+    .unwrap_or_default()
+}
+
+fn seller_history_count(
+    builder: &mut InMemoryWasmTestBuilder,
+    test_context: &TestFixture,
+    seller: AccountHash,
+) -> U256 {
+    query_market_dictionary(
+        builder,
+        test_context,
+        "seller_history_counts",
+        key_to_str(&Key::Account(seller)),
+    )
+    .unwrap_or_default()
+}
+
+fn token_sale_count(
     builder: &mut InMemoryWasmTestBuilder,
     test_context: &TestFixture,
-    owner: Key,
     token_id: TokenId,
-) -> Option<Key> {
-    let contract_hash = test_context.cep47_contract_hash;
-    let dict_name = "allowances";
-    let dictionary_item_key = key_and_value_to_str::<String>(&owner.into(), &token_id.to_string());
+) -> U256 {
+    query_market_dictionary(
+        builder,
+        test_context,
+        "token_sale_counts",
+        key_and_value_to_str(&test_context.cep47_contract_hash, &token_id),
+    )
+    .unwrap_or_default()
+}
 
-    match query_dictionary_item(builder, contract_hash, dict_name, dictionary_item_key) {
+fn token_sale_by_index(
+    builder: &mut InMemoryWasmTestBuilder,
+    test_context: &TestFixture,
+    token_id: TokenId,
+    index: U256,
+) -> Option<SaleRecord> {
+    query_market_dictionary(
+        builder,
+        test_context,
+        "token_sales_by_index",
+        key_and_value_to_str(&test_context.cep47_contract_hash, &(token_id, index)),
+    )
+}
+
+fn collection_stats(
+    builder: &mut InMemoryWasmTestBuilder,
+    test_context: &TestFixture,
+) -> CollectionStats {
+    query_market_dictionary(
+        builder,
+        test_context,
+        "collection_stats",
+        key_to_str(&test_context.cep47_contract_hash),
+    )
+    .unwrap_or_default()
+}
+
+fn query_market_dictionary<T: FromBytes + CLTyped>(
+    builder: &mut InMemoryWasmTestBuilder,
+    test_context: &TestFixture,
+    dictionary_name: &str,
+    dictionary_item_key: String,
+) -> Option<T> {
+    match query_dictionary_item(
+        builder,
+        test_context.market_contract_hash,
+        dictionary_name,
+        dictionary_item_key,
+    ) {
         Ok(value) => value
             .as_cl_value()
             .expect("should be cl value.")
@@ -462,90 +730,343 @@ fn get_approved(
     }
 }
 
-fn approve(
+fn ces_events_length(builder: &mut InMemoryWasmTestBuilder, test_context: &TestFixture) -> u32 {
+    query(
+        builder,
+        Key::Account(test_context.owner.account_hash),
+        &[
+            MARKET_CONTRACT_HASH_KEY.to_string(),
+            "__events_length".to_string(),
+        ],
+    )
+}
+
+fn ces_event(
+    builder: &mut InMemoryWasmTestBuilder,
+    test_context: &TestFixture,
+    index: u32,
+) -> Option<Bytes> {
+    // Casper Event Standard records are stored bare, not wrapped in an `Option`.
+    query_dictionary_item(
+        builder,
+        test_context.market_contract_hash,
+        "__events",
+        index.to_string(),
+    )
+    .ok()
+    .map(|value| {
+        value
+            .as_cl_value()
+            .expect("should be cl value.")
+            .clone()
+            .into_t()
+            .expect("Wrong type in query result.")
+    })
+}
+
+fn market_owner_of(
+    builder: &mut InMemoryWasmTestBuilder,
+    test_context: &TestFixture,
+    item_id: TokenId,
+) -> Option<Key> {
+    market_item(builder, test_context, item_id).map(|item| item.seller)
+}
+
+fn next_item_id(builder: &mut InMemoryWasmTestBuilder, test_context: &TestFixture) -> TokenId {
+    query(
+        builder,
+        Key::Account(test_context.owner.account_hash),
+        &[
+            MARKET_CONTRACT_HASH_KEY.to_string(),
+            "next_item_id".to_string(),
+        ],
+    )
+}
+
+fn call_market(
     builder: &mut InMemoryWasmTestBuilder,
     test_context: &TestFixture,
     sender: AccountHash,
-    spender: Key,
-    token_ids: Vec<TokenId>,
+    method: &str,
+    args: RuntimeArgs,
 ) {
-    let method: &str = "approve";
-    let source = DeploySource::ByHash {
-        hash: ContractHash::from(test_context.cep47_contract_hash.into_hash().unwrap()),
-        method: method.to_string(),
-    };
-    let args = runtime_args! {
-            "spender" => spender,
-            "token_ids" => token_ids,
-    };
-    let mut deploy_builder = DeployItemBuilder::new()
+    let deploy_item = DeployItemBuilder::new()
         .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
         .with_address(sender)
-        .with_authorization_keys(&[sender]);
-    deploy_builder = match source {
-        DeploySource::Code(path) => deploy_builder.with_session_code(path, args),
-        DeploySource::ByHash { hash, method } => {
-            // let contract_hash = ContractHash::from(*hash);
-            deploy_builder.with_stored_session_hash(hash, &*method, args)
-        }
-    };
-
-    let mut execute_request_builder =
-        ExecuteRequestBuilder::from_deploy_item(deploy_builder.build());
+        .with_authorization_keys(&[sender])
+        .with_stored_session_hash(
+            ContractHash::from(test_context.market_contract_hash.into_hash().unwrap()),
+            method,
+            args,
+        )
+        .build();
     builder
-        .exec(execute_request_builder.build())
+        .exec(ExecuteRequestBuilder::from_deploy_item(deploy_item).build())
         .expect_success()
         .commit();
 }
 
-fn transfer_from(
+/// Calls the market's `entry_point` through `market-query.wasm` and decodes what it returned.
+fn query_market<T: FromBytes>(
+    builder: &mut InMemoryWasmTestBuilder,
+    test_context: &TestFixture,
+    entry_point: &str,
+    args: RuntimeArgs,
+) -> T {
+    let caller = test_context.owner.account_hash;
+    let exec_request = ExecuteRequestBuilder::standard(
+        caller,
+        MARKET_QUERY_WASM,
+        runtime_args! {
+            "contract_hash" => ContractHash::from(test_context.market_contract_hash.into_hash().unwrap()),
+            "entry_point" => entry_point,
+            "args" => Bytes::from(args.to_bytes().unwrap()),
+        },
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+    let result: Bytes = query(builder, Key::Account(caller), &["query_result".to_string()]);
+    let (value, rest) = T::from_bytes(&result).expect("should decode query result.");
+    assert!(rest.is_empty());
+    value
+}
+
+/// Mints `count` tokens to `seller` and lists them all, so item `i` sells token `i` for
+/// `100 * (i + 1)`.
+fn mint_and_list_many(
+    builder: &mut InMemoryWasmTestBuilder,
+    test_context: &TestFixture,
+    seller: AccountHash,
+    count: u64,
+) {
+    let token_ids: Vec<TokenId> = (0..count).map(TokenId::from).collect();
+    nft_mint(
+        builder,
+        test_context,
+        test_context.owner.account_hash,
+        seller,
+        token_ids.clone(),
+        vec![meta::red_dragon(); token_ids.len()],
+    );
+    approve_market(builder, test_context, seller, token_ids.clone());
+    create_market_item(
+        builder,
+        test_context,
+        seller,
+        Key::Account(seller),
+        token_ids,
+        (1..=count).map(|i| U512::from(100 * i)).collect(),
+    );
+}
+
+fn call_cep47(
     builder: &mut InMemoryWasmTestBuilder,
     test_context: &TestFixture,
     sender: AccountHash,
-    owner: AccountHash,
-    recipient: AccountHash,
+    method: &str,
+    args: RuntimeArgs,
 ) {
-    let method: &str = "transfer_from";
-    let source = DeploySource::ByHash {
-        hash: ContractHash::from(test_context.cep47_contract_hash.into_hash().unwrap()),
-        method: method.to_string(),
-    };
-    let args = runtime_args! {
-            "sender" => Key::Account(owner),
-            "recipient" => Key::Account(recipient),
-            "token_ids" => vec![TokenId::zero()],
-    };
-    let mut deploy_builder = DeployItemBuilder::new()
+    let deploy_item = DeployItemBuilder::new()
         .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
         .with_address(sender)
-        .with_authorization_keys(&[sender]);
-    deploy_builder = match source {
-        DeploySource::Code(path) => deploy_builder.with_session_code(path, args),
-        DeploySource::ByHash { hash, method } => {
-            // let contract_hash = ContractHash::from(*hash);
-            deploy_builder.with_stored_session_hash(hash, &*method, args)
-        }
-    };
-
-    let mut execute_request_builder =
-        ExecuteRequestBuilder::from_deploy_item(deploy_builder.build());
+        .with_authorization_keys(&[sender])
+        .with_stored_session_hash(
+            ContractHash::from(test_context.cep47_contract_hash.into_hash().unwrap()),
+            method,
+            args,
+        )
+        .build();
     builder
-        .exec(execute_request_builder.build())
+        .exec(ExecuteRequestBuilder::from_deploy_item(deploy_item).build())
         .expect_success()
         .commit();
 }
 
-#[test]
-fn should_process_valid_nft_sale() {
-    let (mut builder, test_context, mut accounts) = setup();
-    let seller = accounts.pop().unwrap();
-    let buyer = accounts.pop().unwrap();
-    // println!("owner           {:?}", test_context.owner.account_hash);
-    // println!("seller          {:?}", &seller.account_hash);
-    // println!("buyer           {:?}", &buyer.account_hash);
-    // println!(
-    //     "cep47_contract_hash     {:?}",
-    //     test_context.cep47_contract_hash
+fn set_collection_capabilities(
+    builder: &mut InMemoryWasmTestBuilder,
+    test_context: &TestFixture,
+    capabilities: u8,
+) {
+    call_market(
+        builder,
+        test_context,
+        test_context.owner.account_hash,
+        "set_collection_capabilities",
+        runtime_args! {
+            "nft_contract_address" => ContractHash::from(test_context.cep47_contract_hash.into_hash().unwrap()),
+            "capabilities" => capabilities,
+        },
+    );
+}
+
+fn admin_burn(
+    builder: &mut InMemoryWasmTestBuilder,
+    test_context: &TestFixture,
+    token_ids: Vec<TokenId>,
+) {
+    call_cep47(
+        builder,
+        test_context,
+        test_context.owner.account_hash,
+        "admin_burn",
+        runtime_args! {
+            "token_ids" => token_ids,
+            "reason" => "infringing"
+        },
+    );
+}
+
+fn market_item_hash(builder: &mut InMemoryWasmTestBuilder, test_context: &TestFixture) -> Key {
+    query(
+        builder,
+        Key::Account(test_context.owner.account_hash),
+        &[
+            MARKET_CONTRACT_HASH_KEY.to_string(),
+            "market_item_hash".to_string(),
+        ],
+    )
+}
+
+fn account_balance(builder: &mut InMemoryWasmTestBuilder, account: AccountHash) -> U512 {
+    let purse = builder
+        .get_account(account)
+        .expect("should have account")
+        .main_purse();
+    builder.get_purse_balance(purse)
+}
+
+fn owner_of(
+    builder: &mut InMemoryWasmTestBuilder,
+    test_context: &TestFixture,
+    token_id: TokenId,
+) -> Option<Key> {
+    match query_dictionary_item(
+        builder,
+        test_context.cep47_contract_hash,
+        "owners",
+        TokenId::zero().to_string(),
+    ) {
+        Ok(value) => value
+            .as_cl_value()
+            .expect("should be cl value.")
+            .clone()
+            .into_t()
+            .expect("Wrong type in query result."),
+        Err(e) => {
+            println!("{}", e);
+            None
+        }
+    }
+}
+
+fn get_approved(
+    builder: &mut InMemoryWasmTestBuilder,
+    test_context: &TestFixture,
+    owner: Key,
+    token_id: TokenId,
+) -> Option<Key> {
+    let contract_hash = test_context.cep47_contract_hash;
+    let dict_name = "allowances";
+    let dictionary_item_key = key_and_value_to_str::<String>(&owner.into(), &token_id.to_string());
+
+    match query_dictionary_item(builder, contract_hash, dict_name, dictionary_item_key) {
+        Ok(value) => value
+            .as_cl_value()
+            .expect("should be cl value.")
+            .clone()
+            .into_t()
+            .expect("Wrong type in query result."),
+        Err(e) => {
+            println!("{}", e);
+            None
+        }
+    }
+}
+
+fn approve(
+    builder: &mut InMemoryWasmTestBuilder,
+    test_context: &TestFixture,
+    sender: AccountHash,
+    spender: Key,
+    token_ids: Vec<TokenId>,
+) {
+    let method: &str = "approve";
+    let source = DeploySource::ByHash {
+        hash: ContractHash::from(test_context.cep47_contract_hash.into_hash().unwrap()),
+        method: method.to_string(),
+    };
+    let args = runtime_args! {
+            "spender" => spender,
+            "token_ids" => token_ids,
+    };
+    let mut deploy_builder = DeployItemBuilder::new()
+        .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
+        .with_address(sender)
+        .with_authorization_keys(&[sender]);
+    deploy_builder = match source {
+        DeploySource::Code(path) => deploy_builder.with_session_code(path, args),
+        DeploySource::ByHash { hash, method } => {
+            // let contract_hash = ContractHash::from(*hash);
+            deploy_builder.with_stored_session_hash(hash, &*method, args)
+        }
+    };
+
+    let mut execute_request_builder =
+        ExecuteRequestBuilder::from_deploy_item(deploy_builder.build());
+    builder
+        .exec(execute_request_builder.build())
+        .expect_success()
+        .commit();
+}
+
+fn transfer_from(
+    builder: &mut InMemoryWasmTestBuilder,
+    test_context: &TestFixture,
+    sender: AccountHash,
+    owner: AccountHash,
+    recipient: AccountHash,
+) {
+    let method: &str = "transfer_from";
+    let source = DeploySource::ByHash {
+        hash: ContractHash::from(test_context.cep47_contract_hash.into_hash().unwrap()),
+        method: method.to_string(),
+    };
+    let args = runtime_args! {
+            "sender" => Key::Account(owner),
+            "recipient" => Key::Account(recipient),
+            "token_ids" => vec![TokenId::zero()],
+    };
+    let mut deploy_builder = DeployItemBuilder::new()
+        .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
+        .with_address(sender)
+        .with_authorization_keys(&[sender]);
+    deploy_builder = match source {
+        DeploySource::Code(path) => deploy_builder.with_session_code(path, args),
+        DeploySource::ByHash { hash, method } => {
+            // let contract_hash = ContractHash::from(*hash);
+            deploy_builder.with_stored_session_hash(hash, &*method, args)
+        }
+    };
+
+    let mut execute_request_builder =
+        ExecuteRequestBuilder::from_deploy_item(deploy_builder.build());
+    builder
+        .exec(execute_request_builder.build())
+        .expect_success()
+        .commit();
+}
+
+#[test]
+fn should_process_valid_nft_sale() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let buyer = accounts.pop().unwrap();
+    // println!("owner           {:?}", test_context.owner.account_hash);
+    // println!("seller          {:?}", &seller.account_hash);
+    // println!("buyer           {:?}", &buyer.account_hash);
+    // println!(
+    //     "cep47_contract_hash     {:?}",
+    //     test_context.cep47_contract_hash
     // );
     // println!(
     //     "cep47_contract_package_hash     {:?}",
@@ -609,15 +1130,6 @@ fn should_process_valid_nft_sale() {
     // --------------- Using contract to transfer --------------- //
     let amount: U512 = 12345.into();
 
-    create_market_item(
-        &mut builder,
-        &test_context,
-        seller.account_hash,
-        Key::Account(seller.account_hash),
-        vec![TokenId::zero()],
-        vec![amount],
-    );
-
     let market_function_hash = builder
         .query(
             None,
@@ -653,6 +1165,14 @@ fn should_process_valid_nft_sale() {
     );
     // println!("get_approved_result {:?}", get_approved_result);
     assert_eq!(get_approved_result.unwrap(), market_function_hash);
+    create_market_item(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        Key::Account(seller.account_hash),
+        vec![TokenId::zero()],
+        vec![amount],
+    );
     process_market_sale(
         &mut builder,
         &test_context,
@@ -668,14 +1188,2018 @@ fn should_process_valid_nft_sale() {
     assert_eq!(owner_after.unwrap(), Key::Account(buyer.account_hash));
 }
 
-#[ignore]
 #[test]
-fn test_should_fail_sell_market_item_insufficient_funds() {}
+fn should_store_market_item_record() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let buyer = accounts.pop().unwrap();
+    let amount: U512 = 12345.into();
+
+    nft_mint(
+        &mut builder,
+        &test_context,
+        test_context.owner.account_hash,
+        seller.account_hash,
+        vec![TokenId::zero()],
+        vec![meta::red_dragon()],
+    );
+    approve_market(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        vec![TokenId::zero()],
+    );
+    create_market_item(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        Key::Account(seller.account_hash),
+        vec![TokenId::zero()],
+        vec![amount],
+    );
+
+    let item = market_item(&mut builder, &test_context, TokenId::zero()).unwrap();
+    assert_eq!(item.item_id, TokenId::zero());
+    assert_eq!(item.seller, Key::Account(seller.account_hash));
+    assert_eq!(
+        Key::Hash(item.nft_contract_address.value()),
+        test_context.cep47_contract_hash
+    );
+    assert_eq!(item.token_id, TokenId::zero());
+    assert_eq!(item.asking_price, amount);
+    assert_eq!(item.status, MarketItemStatus::Available);
+
+    process_market_sale(
+        &mut builder,
+        &test_context,
+        Key::Account(buyer.account_hash),
+        buyer.account_hash,
+        TokenId::zero(),
+        amount,
+    );
+
+    let item = market_item(&mut builder, &test_context, TokenId::zero()).unwrap();
+    assert_eq!(item.seller, Key::Account(seller.account_hash));
+    assert_eq!(item.status, MarketItemStatus::Sold);
+}
+
+#[test]
+fn should_return_market_info() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let fee_recipient = accounts.pop().unwrap();
+    mint_and_list_many(&mut builder, &test_context, seller.account_hash, 2);
+    call_market(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        "cancel_market_item",
+        runtime_args! { "item_id" => TokenId::zero() },
+    );
+    call_market(
+        &mut builder,
+        &test_context,
+        test_context.owner.account_hash,
+        "set_fee_config",
+        runtime_args! {
+            "fee_basis_points" => 250u32,
+            "fee_recipient" => Key::Account(fee_recipient.account_hash),
+        },
+    );
+
+    let info: (
+        (ContractHash, ContractPackageHash),
+        (u32, Key),
+        (U256, U256),
+    ) = query_market(&mut builder, &test_context, "market_info", runtime_args! {});
+    let ((contract_hash, contract_package_hash), fee_config, supply) = info;
+    assert_eq!(
+        contract_hash,
+        ContractHash::from(test_context.market_contract_hash.into_hash().unwrap())
+    );
+    // `market_item_hash` holds the package hash approvals name as spender.
+    assert_eq!(
+        Key::Hash(contract_package_hash.value()),
+        market_item_hash(&mut builder, &test_context)
+    );
+    assert_eq!(fee_config, (250, Key::Account(fee_recipient.account_hash)));
+    assert_eq!(supply, (2.into(), U256::one()));
+}
 
-#[ignore]
 #[test]
-fn test_should_fail_sell_market_item_not_available() {}
+fn should_return_market_item() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    mint_and_list(&mut builder, &test_context, seller.account_hash, 12345.into());
+
+    let item: Option<MarketItem> = query_market(
+        &mut builder,
+        &test_context,
+        "get_market_item",
+        runtime_args! { "item_id" => TokenId::zero() },
+    );
+    assert_eq!(
+        item,
+        Some(MarketItem {
+            item_id: TokenId::zero(),
+            seller: Key::Account(seller.account_hash),
+            nft_contract_address: ContractHash::from(
+                test_context.cep47_contract_hash.into_hash().unwrap()
+            ),
+            token_id: TokenId::zero(),
+            asking_price: 12345.into(),
+            status: MarketItemStatus::Available,
+        })
+    );
+
+    call_market(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        "cancel_market_item",
+        runtime_args! { "item_id" => TokenId::zero() },
+    );
+    let item: Option<MarketItem> = query_market(
+        &mut builder,
+        &test_context,
+        "get_market_item",
+        runtime_args! { "item_id" => TokenId::zero() },
+    );
+    assert_eq!(item.unwrap().status, MarketItemStatus::Cancelled);
+
+    let item: Option<MarketItem> = query_market(
+        &mut builder,
+        &test_context,
+        "get_market_item",
+        runtime_args! { "item_id" => TokenId::one() },
+    );
+    assert_eq!(item, None);
+}
 
-#[ignore]
 #[test]
-fn test_cancel_market_item() {}
+fn should_index_active_listings() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let token_ids: Vec<TokenId> = vec![0.into(), 1.into(), 2.into()];
+    nft_mint(
+        &mut builder,
+        &test_context,
+        test_context.owner.account_hash,
+        seller.account_hash,
+        token_ids.clone(),
+        vec![meta::red_dragon(), meta::blue_dragon(), meta::red_dragon()],
+    );
+    approve_market(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        token_ids.clone(),
+    );
+    create_market_item(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        Key::Account(seller.account_hash),
+        token_ids,
+        vec![100.into(), 200.into(), 300.into()],
+    );
+    assert_eq!(active_items_count(&mut builder, &test_context), 3.into());
+    assert_eq!(collection_items_count(&mut builder, &test_context), 3.into());
+
+    call_market(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        "cancel_market_item",
+        runtime_args! { "item_id" => TokenId::zero() },
+    );
+
+    // The last listing is swapped into the freed slot.
+    assert_eq!(active_items_count(&mut builder, &test_context), 2.into());
+    assert_eq!(collection_items_count(&mut builder, &test_context), 2.into());
+    assert_eq!(
+        active_item_by_index(&mut builder, &test_context, 0.into()),
+        Some(2.into())
+    );
+    assert_eq!(
+        active_item_by_index(&mut builder, &test_context, 1.into()),
+        Some(1.into())
+    );
+    assert_eq!(
+        active_item_by_index(&mut builder, &test_context, 2.into()),
+        None
+    );
+}
+
+fn item_ids(items: &[MarketItem]) -> Vec<TokenId> {
+    items.iter().map(|item| item.item_id).collect()
+}
+
+#[test]
+fn should_page_active_listings() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    mint_and_list_many(&mut builder, &test_context, seller.account_hash, 3);
+
+    let page: Vec<MarketItem> = query_market(
+        &mut builder,
+        &test_context,
+        "active_listings",
+        runtime_args! { "offset" => 1u64, "limit" => 10u64 },
+    );
+    assert_eq!(item_ids(&page), vec![1.into(), 2.into()]);
+    assert_eq!(page[0].seller, Key::Account(seller.account_hash));
+    assert_eq!(page[0].asking_price, U512::from(200));
+    assert_eq!(page[0].status, MarketItemStatus::Available);
+
+    let page: Vec<MarketItem> = query_market(
+        &mut builder,
+        &test_context,
+        "active_listings",
+        runtime_args! { "offset" => 0u64, "limit" => 2u64 },
+    );
+    assert_eq!(item_ids(&page), vec![0.into(), 1.into()]);
+
+    let page: Vec<MarketItem> = query_market(
+        &mut builder,
+        &test_context,
+        "active_listings",
+        runtime_args! { "offset" => 0u64, "limit" => 0u64 },
+    );
+    assert!(page.is_empty());
+
+    let page: Vec<MarketItem> = query_market(
+        &mut builder,
+        &test_context,
+        "active_listings",
+        runtime_args! { "offset" => 3u64, "limit" => 10u64 },
+    );
+    assert!(page.is_empty());
+}
+
+#[test]
+fn should_cap_listing_pages_to_max_page_size() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    mint_and_list_many(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        MAX_PAGE_SIZE + 1,
+    );
+    let nft_contract_address =
+        ContractHash::from(test_context.cep47_contract_hash.into_hash().unwrap());
+
+    let page: Vec<MarketItem> = query_market(
+        &mut builder,
+        &test_context,
+        "active_listings",
+        runtime_args! { "offset" => 0u64, "limit" => u64::MAX },
+    );
+    assert_eq!(page.len() as u64, MAX_PAGE_SIZE);
+
+    let page: Vec<MarketItem> = query_market(
+        &mut builder,
+        &test_context,
+        "listings_by_seller",
+        runtime_args! {
+            "owner" => Key::Account(seller.account_hash),
+            "offset" => 0u64,
+            "limit" => MAX_PAGE_SIZE + 1,
+        },
+    );
+    assert_eq!(page.len() as u64, MAX_PAGE_SIZE);
+
+    let page: Vec<MarketItem> = query_market(
+        &mut builder,
+        &test_context,
+        "listings_by_collection",
+        runtime_args! {
+            "nft_contract_address" => nft_contract_address,
+            "offset" => 0u64,
+            "limit" => MAX_PAGE_SIZE + 1,
+        },
+    );
+    assert_eq!(page.len() as u64, MAX_PAGE_SIZE);
+
+    // The remainder is reachable on the next page.
+    let page: Vec<MarketItem> = query_market(
+        &mut builder,
+        &test_context,
+        "active_listings",
+        runtime_args! { "offset" => MAX_PAGE_SIZE, "limit" => MAX_PAGE_SIZE },
+    );
+    assert_eq!(item_ids(&page), vec![TokenId::from(MAX_PAGE_SIZE)]);
+}
+
+#[test]
+fn should_page_listings_after_swap_remove() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let other_seller = accounts.pop().unwrap();
+    mint_and_list_many(&mut builder, &test_context, seller.account_hash, 3);
+    let nft_contract_address =
+        ContractHash::from(test_context.cep47_contract_hash.into_hash().unwrap());
+
+    call_market(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        "cancel_market_item",
+        runtime_args! { "item_id" => TokenId::zero() },
+    );
+
+    // Each index moves its last listing into the freed slot.
+    let page: Vec<MarketItem> = query_market(
+        &mut builder,
+        &test_context,
+        "active_listings",
+        runtime_args! { "offset" => 0u64, "limit" => 10u64 },
+    );
+    assert_eq!(item_ids(&page), vec![2.into(), 1.into()]);
+
+    let page: Vec<MarketItem> = query_market(
+        &mut builder,
+        &test_context,
+        "listings_by_seller",
+        runtime_args! {
+            "owner" => Key::Account(seller.account_hash),
+            "offset" => 0u64,
+            "limit" => 10u64,
+        },
+    );
+    assert_eq!(item_ids(&page), vec![2.into(), 1.into()]);
+
+    let page: Vec<MarketItem> = query_market(
+        &mut builder,
+        &test_context,
+        "listings_by_seller",
+        runtime_args! {
+            "owner" => Key::Account(seller.account_hash),
+            "offset" => 1u64,
+            "limit" => 10u64,
+        },
+    );
+    assert_eq!(item_ids(&page), vec![1.into()]);
+
+    let page: Vec<MarketItem> = query_market(
+        &mut builder,
+        &test_context,
+        "listings_by_seller",
+        runtime_args! {
+            "owner" => Key::Account(other_seller.account_hash),
+            "offset" => 0u64,
+            "limit" => 10u64,
+        },
+    );
+    assert!(page.is_empty());
+
+    let page: Vec<MarketItem> = query_market(
+        &mut builder,
+        &test_context,
+        "listings_by_collection",
+        runtime_args! {
+            "nft_contract_address" => nft_contract_address,
+            "offset" => 0u64,
+            "limit" => 10u64,
+        },
+    );
+    assert_eq!(item_ids(&page), vec![2.into(), 1.into()]);
+    assert!(page.iter().all(|item| item.status == MarketItemStatus::Available));
+}
+
+#[test]
+fn should_remove_sold_item_from_active_listings() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let buyer = accounts.pop().unwrap();
+    let amount: U512 = 12345.into();
+    mint_and_list(&mut builder, &test_context, seller.account_hash, amount);
+    assert_eq!(active_items_count(&mut builder, &test_context), 1.into());
+
+    process_market_sale(
+        &mut builder,
+        &test_context,
+        Key::Account(buyer.account_hash),
+        buyer.account_hash,
+        TokenId::zero(),
+        amount,
+    );
+
+    assert_eq!(active_items_count(&mut builder, &test_context), 0.into());
+    assert_eq!(collection_items_count(&mut builder, &test_context), 0.into());
+    assert_eq!(
+        active_item_by_index(&mut builder, &test_context, 0.into()),
+        None
+    );
+}
+
+#[test]
+fn should_track_active_listings_through_status_changes() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    mint_and_list(&mut builder, &test_context, seller.account_hash, 12345.into());
+    let set_status = |builder: &mut InMemoryWasmTestBuilder, status: MarketItemStatus| {
+        call_market(
+            builder,
+            &test_context,
+            test_context.owner.account_hash,
+            "set_item_status",
+            runtime_args! {
+                "item_id" => TokenId::zero(),
+                "status" => status as u8,
+            },
+        );
+    };
+
+    set_status(&mut builder, MarketItemStatus::InAuction);
+    assert_eq!(active_items_count(&mut builder, &test_context), 0.into());
+
+    set_status(&mut builder, MarketItemStatus::Available);
+    assert_eq!(active_items_count(&mut builder, &test_context), 1.into());
+    assert_eq!(
+        active_item_by_index(&mut builder, &test_context, 0.into()),
+        Some(TokenId::zero())
+    );
+
+    set_status(&mut builder, MarketItemStatus::Expired);
+    assert_eq!(active_items_count(&mut builder, &test_context), 0.into());
+    assert_eq!(collection_items_count(&mut builder, &test_context), 0.into());
+}
+
+#[test]
+fn should_remove_invalidated_item_from_active_listings() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    mint_and_list(&mut builder, &test_context, seller.account_hash, 12345.into());
+
+    call_market(
+        &mut builder,
+        &test_context,
+        test_context.owner.account_hash,
+        "set_item_status",
+        runtime_args! {
+            "item_id" => TokenId::zero(),
+            "status" => MarketItemStatus::Invalid as u8,
+        },
+    );
+
+    assert_eq!(active_items_count(&mut builder, &test_context), 0.into());
+}
+
+#[test]
+fn should_move_closed_listings_to_seller_history() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let buyer = accounts.pop().unwrap();
+    let amount: U512 = 12345.into();
+    let token_ids: Vec<TokenId> = vec![0.into(), 1.into()];
+    nft_mint(
+        &mut builder,
+        &test_context,
+        test_context.owner.account_hash,
+        seller.account_hash,
+        token_ids.clone(),
+        vec![meta::red_dragon(), meta::blue_dragon()],
+    );
+    approve_market(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        token_ids.clone(),
+    );
+    create_market_item(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        Key::Account(seller.account_hash),
+        token_ids.clone(),
+        vec![amount, amount],
+    );
+    assert_eq!(
+        seller_listings_count(&mut builder, &test_context, seller.account_hash),
+        2.into()
+    );
+    assert_eq!(
+        seller_history_count(&mut builder, &test_context, seller.account_hash),
+        0.into()
+    );
+
+    process_market_sale(
+        &mut builder,
+        &test_context,
+        Key::Account(buyer.account_hash),
+        buyer.account_hash,
+        TokenId::zero(),
+        amount,
+    );
+    assert_eq!(
+        seller_listings_count(&mut builder, &test_context, seller.account_hash),
+        1.into()
+    );
+    assert_eq!(
+        seller_history_count(&mut builder, &test_context, seller.account_hash),
+        1.into()
+    );
+
+    call_market(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        "cancel_market_item",
+        runtime_args! { "item_id" => TokenId::one() },
+    );
+    assert_eq!(
+        seller_listings_count(&mut builder, &test_context, seller.account_hash),
+        0.into()
+    );
+    assert_eq!(
+        seller_history_count(&mut builder, &test_context, seller.account_hash),
+        2.into()
+    );
+}
+
+#[test]
+fn should_record_token_sale_history() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let buyer = accounts.pop().unwrap();
+    let amount: U512 = 12345.into();
+    mint_and_list(&mut builder, &test_context, seller.account_hash, amount);
+    assert_eq!(
+        token_sale_count(&mut builder, &test_context, TokenId::zero()),
+        0.into()
+    );
+
+    process_market_sale(
+        &mut builder,
+        &test_context,
+        Key::Account(buyer.account_hash),
+        buyer.account_hash,
+        TokenId::zero(),
+        amount,
+    );
+
+    assert_eq!(
+        token_sale_count(&mut builder, &test_context, TokenId::zero()),
+        1.into()
+    );
+    let sale = token_sale_by_index(&mut builder, &test_context, TokenId::zero(), 0.into())
+        .unwrap();
+    assert_eq!(sale.item_id, TokenId::zero());
+    assert_eq!(sale.seller, Key::Account(seller.account_hash));
+    assert_eq!(sale.buyer, Key::Account(buyer.account_hash));
+    assert_eq!(sale.price, amount);
+}
+
+#[test]
+fn should_clear_market_approval_after_sale() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let buyer = accounts.pop().unwrap();
+    let amount: U512 = 12345.into();
+    mint_and_list(&mut builder, &test_context, seller.account_hash, amount);
+
+    process_market_sale(
+        &mut builder,
+        &test_context,
+        Key::Account(buyer.account_hash),
+        buyer.account_hash,
+        TokenId::zero(),
+        amount,
+    );
+
+    assert_eq!(
+        get_approved(
+            &mut builder,
+            &test_context,
+            Key::Account(seller.account_hash),
+            TokenId::zero(),
+        ),
+        None
+    );
+}
+
+/// Sends `token_ids` to the market with `safe_transfer`, listing them at `asking_prices`.
+fn safe_transfer_to_market(
+    builder: &mut InMemoryWasmTestBuilder,
+    test_context: &TestFixture,
+    seller: AccountHash,
+    token_ids: Vec<TokenId>,
+    asking_prices: Vec<U512>,
+) {
+    let market = market_item_hash(builder, test_context);
+    call_cep47(
+        builder,
+        test_context,
+        seller,
+        "safe_transfer",
+        runtime_args! {
+            "recipient" => market,
+            "token_ids" => token_ids,
+            "data" => Bytes::from(asking_prices.to_bytes().unwrap()),
+        },
+    );
+}
+
+#[test]
+fn should_accept_safe_transfer_into_custody() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let amount: U512 = 12345.into();
+    nft_mint(
+        &mut builder,
+        &test_context,
+        test_context.owner.account_hash,
+        seller.account_hash,
+        vec![TokenId::zero()],
+        vec![meta::red_dragon()],
+    );
+    let market = market_item_hash(&mut builder, &test_context);
+
+    safe_transfer_to_market(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        vec![TokenId::zero()],
+        vec![amount],
+    );
+
+    let owner_after = owner_of(&mut builder, &test_context, TokenId::zero());
+    assert_eq!(owner_after.unwrap(), market);
+    let item = market_item(&mut builder, &test_context, TokenId::zero()).unwrap();
+    assert_eq!(item.seller, Key::Account(seller.account_hash));
+    assert_eq!(item.asking_price, amount);
+    assert_eq!(item.status, MarketItemStatus::Available);
+    assert_eq!(active_items_count(&mut builder, &test_context), U256::one());
+
+    // Cancelling the listing withdraws the token from custody.
+    call_market(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        "cancel_market_item",
+        runtime_args! {
+            "item_id" => TokenId::zero(),
+        },
+    );
+
+    let owner_after = owner_of(&mut builder, &test_context, TokenId::zero());
+    assert_eq!(owner_after.unwrap(), Key::Account(seller.account_hash));
+    let item = market_item(&mut builder, &test_context, TokenId::zero()).unwrap();
+    assert_eq!(item.status, MarketItemStatus::Cancelled);
+}
+
+#[test]
+fn should_sell_token_from_custody() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let buyer = accounts.pop().unwrap();
+    let amount: U512 = 12345.into();
+    nft_mint(
+        &mut builder,
+        &test_context,
+        test_context.owner.account_hash,
+        seller.account_hash,
+        vec![TokenId::zero()],
+        vec![meta::red_dragon()],
+    );
+    safe_transfer_to_market(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        vec![TokenId::zero()],
+        vec![amount],
+    );
+
+    let seller_balance_before = account_balance(&mut builder, seller.account_hash);
+    process_market_sale(
+        &mut builder,
+        &test_context,
+        Key::Account(buyer.account_hash),
+        buyer.account_hash,
+        TokenId::zero(),
+        amount,
+    );
+
+    let owner_after = owner_of(&mut builder, &test_context, TokenId::zero());
+    assert_eq!(owner_after.unwrap(), Key::Account(buyer.account_hash));
+    assert_eq!(
+        account_balance(&mut builder, seller.account_hash) - seller_balance_before,
+        amount
+    );
+    let item = market_item(&mut builder, &test_context, TokenId::zero()).unwrap();
+    assert_eq!(item.status, MarketItemStatus::Sold);
+}
+
+#[test]
+#[should_panic]
+fn should_reject_safe_transfer_without_asking_prices() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    nft_mint(
+        &mut builder,
+        &test_context,
+        test_context.owner.account_hash,
+        seller.account_hash,
+        vec![TokenId::zero()],
+        vec![meta::red_dragon()],
+    );
+    safe_transfer_to_market(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        vec![TokenId::zero()],
+        vec![],
+    );
+}
+
+#[test]
+fn should_keep_deprecated_market_item_hash_entry_point() {
+    let (mut builder, test_context, _) = setup();
+    call_market(
+        &mut builder,
+        &test_context,
+        test_context.owner.account_hash,
+        "market_item_hash",
+        runtime_args! {},
+    );
+}
+
+#[test]
+fn should_not_write_named_keys_during_sale() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let buyer = accounts.pop().unwrap();
+    let amount: U512 = 12345.into();
+    mint_and_list(&mut builder, &test_context, seller.account_hash, amount);
+    process_market_sale(
+        &mut builder,
+        &test_context,
+        Key::Account(buyer.account_hash),
+        buyer.account_hash,
+        TokenId::zero(),
+        amount,
+    );
+
+    let stored_value = builder
+        .query(None, test_context.market_contract_hash, &[])
+        .expect("should be stored value.");
+    let named_keys = match stored_value {
+        StoredValue::Contract(contract) => contract.named_keys().clone(),
+        _ => panic!("market should be a contract"),
+    };
+    assert!(!named_keys.contains_key("balance"));
+    assert!(!named_keys.contains_key("asking_price"));
+    assert!(named_keys.contains_key("market_item_hash"));
+}
+
+#[test]
+fn should_sell_pre_upgrade_listing_after_upgrade() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let buyer = accounts.pop().unwrap();
+    let amount: U512 = 12345.into();
+    mint_and_list(&mut builder, &test_context, seller.account_hash, amount);
+    let spender = market_item_hash(&mut builder, &test_context);
+    let previous_contract_hash = test_context.market_contract_hash;
+
+    let test_context = upgrade_market(&mut builder, test_context);
+    assert_ne!(test_context.market_contract_hash, previous_contract_hash);
+    let schema_version: u32 = query(
+        &mut builder,
+        Key::Account(test_context.owner.account_hash),
+        &[
+            MARKET_CONTRACT_HASH_KEY.to_string(),
+            "schema_version".to_string(),
+        ],
+    );
+    assert_eq!(schema_version, 1);
+    // The package hash, and with it the approved spender, survives the upgrade.
+    assert_eq!(market_item_hash(&mut builder, &test_context), spender);
+    let item = market_item(&mut builder, &test_context, TokenId::zero()).unwrap();
+    assert_eq!(item.status, MarketItemStatus::Available);
+
+    process_market_sale(
+        &mut builder,
+        &test_context,
+        Key::Account(buyer.account_hash),
+        buyer.account_hash,
+        TokenId::zero(),
+        amount,
+    );
+
+    let owner_after = owner_of(&mut builder, &test_context, TokenId::zero());
+    assert_eq!(owner_after.unwrap(), Key::Account(buyer.account_hash));
+    let item = market_item(&mut builder, &test_context, TokenId::zero()).unwrap();
+    assert_eq!(item.status, MarketItemStatus::Sold);
+}
+
+#[test]
+#[should_panic]
+fn should_not_migrate_without_admin_role() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let user = accounts.pop().unwrap();
+    call_market(
+        &mut builder,
+        &test_context,
+        user.account_hash,
+        "migrate",
+        runtime_args! {},
+    );
+}
+
+#[test]
+fn should_update_collection_stats() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let buyer = accounts.pop().unwrap();
+    let token_ids: Vec<TokenId> = vec![0.into(), 1.into(), 2.into()];
+    nft_mint(
+        &mut builder,
+        &test_context,
+        test_context.owner.account_hash,
+        seller.account_hash,
+        token_ids.clone(),
+        vec![meta::red_dragon(), meta::blue_dragon(), meta::red_dragon()],
+    );
+    approve_market(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        token_ids.clone(),
+    );
+    create_market_item(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        Key::Account(seller.account_hash),
+        token_ids.clone(),
+        vec![300.into(), 100.into(), 200.into()],
+    );
+    assert_eq!(
+        collection_stats(&mut builder, &test_context),
+        CollectionStats {
+            volume: U512::zero(),
+            sales_count: U256::zero(),
+            active_listings: 3.into(),
+            floor_price: Some(100.into()),
+        }
+    );
+
+    process_market_sale(
+        &mut builder,
+        &test_context,
+        Key::Account(buyer.account_hash),
+        buyer.account_hash,
+        TokenId::one(),
+        100.into(),
+    );
+
+    assert_eq!(
+        collection_stats(&mut builder, &test_context),
+        CollectionStats {
+            volume: 100.into(),
+            sales_count: U256::one(),
+            active_listings: 2.into(),
+            floor_price: Some(200.into()),
+        }
+    );
+}
+
+#[test]
+fn should_read_sale_history_through_entry_points() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let buyer = accounts.pop().unwrap();
+    let nft_contract_address =
+        ContractHash::from(test_context.cep47_contract_hash.into_hash().unwrap());
+    mint_and_list_many(&mut builder, &test_context, seller.account_hash, 2);
+    process_market_sale(
+        &mut builder,
+        &test_context,
+        Key::Account(buyer.account_hash),
+        buyer.account_hash,
+        TokenId::zero(),
+        100.into(),
+    );
+
+    // The buyer sells token 0 back to the seller as item 2.
+    approve_market(
+        &mut builder,
+        &test_context,
+        buyer.account_hash,
+        vec![TokenId::zero()],
+    );
+    create_market_item(
+        &mut builder,
+        &test_context,
+        buyer.account_hash,
+        Key::Account(buyer.account_hash),
+        vec![TokenId::zero()],
+        vec![250.into()],
+    );
+    process_market_sale(
+        &mut builder,
+        &test_context,
+        Key::Account(seller.account_hash),
+        seller.account_hash,
+        2.into(),
+        250.into(),
+    );
+
+    let last_price: Option<U512> = query_market(
+        &mut builder,
+        &test_context,
+        "last_sale_price",
+        runtime_args! {
+            "nft_contract_address" => nft_contract_address,
+            "token_id" => TokenId::zero(),
+        },
+    );
+    assert_eq!(last_price, Some(250.into()));
+    let last_price: Option<U512> = query_market(
+        &mut builder,
+        &test_context,
+        "last_sale_price",
+        runtime_args! {
+            "nft_contract_address" => nft_contract_address,
+            "token_id" => TokenId::one(),
+        },
+    );
+    assert_eq!(last_price, None);
+
+    let sales: Vec<SaleRecord> = query_market(
+        &mut builder,
+        &test_context,
+        "sale_history",
+        runtime_args! {
+            "nft_contract_address" => nft_contract_address,
+            "token_id" => TokenId::zero(),
+            "offset" => 0u64,
+            "limit" => 10u64,
+        },
+    );
+    assert_eq!(sales.len(), 2);
+    assert_eq!(sales[0].item_id, TokenId::zero());
+    assert_eq!(sales[0].seller, Key::Account(seller.account_hash));
+    assert_eq!(sales[0].buyer, Key::Account(buyer.account_hash));
+    assert_eq!(sales[0].price, U512::from(100));
+    assert_eq!(sales[1].item_id, TokenId::from(2));
+    assert_eq!(sales[1].seller, Key::Account(buyer.account_hash));
+    assert_eq!(sales[1].buyer, Key::Account(seller.account_hash));
+    assert_eq!(sales[1].price, U512::from(250));
+
+    let sales: Vec<SaleRecord> = query_market(
+        &mut builder,
+        &test_context,
+        "sale_history",
+        runtime_args! {
+            "nft_contract_address" => nft_contract_address,
+            "token_id" => TokenId::zero(),
+            "offset" => 1u64,
+            "limit" => 1u64,
+        },
+    );
+    assert_eq!(sales.len(), 1);
+    assert_eq!(sales[0].item_id, TokenId::from(2));
+
+    let history: Vec<MarketItem> = query_market(
+        &mut builder,
+        &test_context,
+        "seller_history",
+        runtime_args! {
+            "owner" => Key::Account(seller.account_hash),
+            "offset" => 0u64,
+            "limit" => 10u64,
+        },
+    );
+    assert_eq!(item_ids(&history), vec![TokenId::zero()]);
+    assert_eq!(history[0].status, MarketItemStatus::Sold);
+    let history: Vec<MarketItem> = query_market(
+        &mut builder,
+        &test_context,
+        "seller_history",
+        runtime_args! {
+            "owner" => Key::Account(buyer.account_hash),
+            "offset" => 0u64,
+            "limit" => 10u64,
+        },
+    );
+    assert_eq!(item_ids(&history), vec![TokenId::from(2)]);
+    assert_eq!(history[0].status, MarketItemStatus::Sold);
+
+    let stats: CollectionStats = query_market(
+        &mut builder,
+        &test_context,
+        "collection_stats",
+        runtime_args! { "nft_contract_address" => nft_contract_address },
+    );
+    assert_eq!(
+        stats,
+        CollectionStats {
+            volume: 350.into(),
+            sales_count: 2.into(),
+            active_listings: U256::one(),
+            floor_price: Some(200.into()),
+        }
+    );
+}
+
+#[test]
+fn should_report_unbacked_listing_as_invalid_on_reads() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let nft_contract_address =
+        ContractHash::from(test_context.cep47_contract_hash.into_hash().unwrap());
+    mint_and_list_many(&mut builder, &test_context, seller.account_hash, 2);
+    admin_burn(&mut builder, &test_context, vec![TokenId::zero()]);
+
+    // Nobody has invalidated the listing yet, so only reads see it as invalid.
+    let stored = market_item(&mut builder, &test_context, TokenId::zero()).unwrap();
+    assert_eq!(stored.status, MarketItemStatus::Available);
+    let item: Option<MarketItem> = query_market(
+        &mut builder,
+        &test_context,
+        "get_market_item",
+        runtime_args! { "item_id" => TokenId::zero() },
+    );
+    assert_eq!(item.unwrap().status, MarketItemStatus::Invalid);
+
+    let page: Vec<MarketItem> = query_market(
+        &mut builder,
+        &test_context,
+        "active_listings",
+        runtime_args! { "offset" => 0u64, "limit" => 10u64 },
+    );
+    assert_eq!(item_ids(&page), vec![0.into(), 1.into()]);
+    assert_eq!(page[0].status, MarketItemStatus::Invalid);
+    assert_eq!(page[1].status, MarketItemStatus::Available);
+
+    let stats: CollectionStats = query_market(
+        &mut builder,
+        &test_context,
+        "collection_stats",
+        runtime_args! { "nft_contract_address" => nft_contract_address },
+    );
+    assert_eq!(stats.active_listings, 2.into());
+    assert_eq!(stats.floor_price, Some(200.into()));
+}
+
+#[test]
+fn should_keep_floor_price_as_listings_close() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let buyer = accounts.pop().unwrap();
+    let token_ids: Vec<TokenId> = (0..5).map(TokenId::from).collect();
+    nft_mint(
+        &mut builder,
+        &test_context,
+        test_context.owner.account_hash,
+        seller.account_hash,
+        token_ids.clone(),
+        vec![meta::red_dragon(); 5],
+    );
+    approve_market(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        token_ids.clone(),
+    );
+    create_market_item(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        Key::Account(seller.account_hash),
+        token_ids,
+        vec![500.into(), 100.into(), 400.into(), 200.into(), 300.into()],
+    );
+    assert_eq!(
+        collection_stats(&mut builder, &test_context).floor_price,
+        Some(100.into())
+    );
+
+    for (item_id, floor_price) in [(1u64, 200u64), (4, 200), (3, 400)] {
+        call_market(
+            &mut builder,
+            &test_context,
+            seller.account_hash,
+            "cancel_market_item",
+            runtime_args! {
+                "item_id" => TokenId::from(item_id),
+            },
+        );
+        assert_eq!(
+            collection_stats(&mut builder, &test_context).floor_price,
+            Some(floor_price.into())
+        );
+    }
+
+    process_market_sale(
+        &mut builder,
+        &test_context,
+        Key::Account(buyer.account_hash),
+        buyer.account_hash,
+        TokenId::from(2),
+        400.into(),
+    );
+    assert_eq!(
+        collection_stats(&mut builder, &test_context).floor_price,
+        Some(500.into())
+    );
+}
+
+#[test]
+fn should_list_with_operator_approval() {
+    // No admin has flagged the default collection's capabilities.
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let buyer = accounts.pop().unwrap();
+    let amount: U512 = 12345.into();
+    let token_ids: Vec<TokenId> = vec![0.into(), 1.into()];
+    nft_mint(
+        &mut builder,
+        &test_context,
+        test_context.owner.account_hash,
+        seller.account_hash,
+        token_ids.clone(),
+        vec![meta::red_dragon(), meta::blue_dragon()],
+    );
+    let operator = market_item_hash(&mut builder, &test_context);
+    call_cep47(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        "set_approval_for_all",
+        runtime_args! {
+            "operator" => operator,
+            "approved" => true,
+        },
+    );
+    create_market_item(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        Key::Account(seller.account_hash),
+        token_ids,
+        vec![amount, amount],
+    );
+    assert_eq!(active_items_count(&mut builder, &test_context), 2.into());
+
+    process_market_sale(
+        &mut builder,
+        &test_context,
+        Key::Account(buyer.account_hash),
+        buyer.account_hash,
+        TokenId::zero(),
+        amount,
+    );
+    let owner_after = owner_of(&mut builder, &test_context, TokenId::zero());
+    assert_eq!(owner_after.unwrap(), Key::Account(buyer.account_hash));
+}
+
+#[test]
+#[should_panic]
+fn should_not_list_without_market_approval() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    nft_mint(
+        &mut builder,
+        &test_context,
+        test_context.owner.account_hash,
+        seller.account_hash,
+        vec![TokenId::zero()],
+        vec![meta::red_dragon()],
+    );
+    create_market_item(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        Key::Account(seller.account_hash),
+        vec![TokenId::zero()],
+        vec![12345.into()],
+    );
+}
+
+#[test]
+fn should_list_and_sell_from_plain_collection() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let buyer = accounts.pop().unwrap();
+    let amount: U512 = 12345.into();
+    let test_context = install_collection(
+        &mut builder,
+        test_context,
+        PLAIN_CEP47_WASM,
+        "plain_cep47",
+        meta::contract_meta(),
+    );
+    set_collection_capabilities(&mut builder, &test_context, 0);
+
+    mint_and_list(&mut builder, &test_context, seller.account_hash, amount);
+    let item = market_item(&mut builder, &test_context, TokenId::zero()).unwrap();
+    assert_eq!(item.status, MarketItemStatus::Available);
+
+    process_market_sale(
+        &mut builder,
+        &test_context,
+        Key::Account(buyer.account_hash),
+        buyer.account_hash,
+        TokenId::zero(),
+        amount,
+    );
+    let owner_after = owner_of(&mut builder, &test_context, TokenId::zero());
+    assert_eq!(owner_after.unwrap(), Key::Account(buyer.account_hash));
+}
+
+#[test]
+#[should_panic]
+fn should_not_list_from_unflagged_plain_collection() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let test_context = install_collection(
+        &mut builder,
+        test_context,
+        PLAIN_CEP47_WASM,
+        "plain_cep47",
+        meta::contract_meta(),
+    );
+
+    // Without flags the market calls `is_transferable`, which plain CEP-47 doesn't implement.
+    mint_and_list(&mut builder, &test_context, seller.account_hash, 12345.into());
+}
+
+#[test]
+#[should_panic(expected = "User(11)")]
+fn should_not_use_operator_approval_without_capability() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    set_collection_capabilities(&mut builder, &test_context, CAPABILITY_TRANSFERABILITY);
+    nft_mint(
+        &mut builder,
+        &test_context,
+        test_context.owner.account_hash,
+        seller.account_hash,
+        vec![TokenId::zero()],
+        vec![meta::red_dragon()],
+    );
+    let operator = market_item_hash(&mut builder, &test_context);
+    call_cep47(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        "set_approval_for_all",
+        runtime_args! {
+            "operator" => operator,
+            "approved" => true,
+        },
+    );
+
+    create_market_item(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        Key::Account(seller.account_hash),
+        vec![TokenId::zero()],
+        vec![12345.into()],
+    );
+}
+
+#[test]
+#[should_panic]
+fn should_not_set_collection_capabilities_without_admin_role() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let user = accounts.pop().unwrap();
+    call_market(
+        &mut builder,
+        &test_context,
+        user.account_hash,
+        "set_collection_capabilities",
+        runtime_args! {
+            "nft_contract_address" => ContractHash::from(test_context.cep47_contract_hash.into_hash().unwrap()),
+            "capabilities" => ALL_CAPABILITIES,
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(1)")]
+fn should_not_list_someone_elses_approved_token() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let victim = accounts.pop().unwrap();
+    let attacker = accounts.pop().unwrap();
+    nft_mint(
+        &mut builder,
+        &test_context,
+        test_context.owner.account_hash,
+        victim.account_hash,
+        vec![TokenId::zero()],
+        vec![meta::red_dragon()],
+    );
+    let operator = market_item_hash(&mut builder, &test_context);
+    call_cep47(
+        &mut builder,
+        &test_context,
+        victim.account_hash,
+        "set_approval_for_all",
+        runtime_args! {
+            "operator" => operator,
+            "approved" => true,
+        },
+    );
+
+    create_market_item(
+        &mut builder,
+        &test_context,
+        attacker.account_hash,
+        Key::Account(victim.account_hash),
+        vec![TokenId::zero()],
+        vec![1.into()],
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(12)")]
+fn should_not_list_non_transferable_token() {
+    // Unflagged collections are checked for soulbound tokens up front.
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    call_cep47(
+        &mut builder,
+        &test_context,
+        test_context.owner.account_hash,
+        "mint_non_transferable",
+        runtime_args! {
+            "recipient" => Key::Account(seller.account_hash),
+            "token_ids" => vec![TokenId::zero()],
+            "token_metas" => vec![meta::red_dragon()],
+        },
+    );
+    let operator = market_item_hash(&mut builder, &test_context);
+    call_cep47(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        "set_approval_for_all",
+        runtime_args! {
+            "operator" => operator,
+            "approved" => true,
+        },
+    );
+
+    create_market_item(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        Key::Account(seller.account_hash),
+        vec![TokenId::zero()],
+        vec![12345.into()],
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(11)")]
+fn should_not_list_non_transferable_token_without_transferability_capability() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    set_collection_capabilities(&mut builder, &test_context, CAPABILITY_OPERATOR_APPROVALS);
+    call_cep47(
+        &mut builder,
+        &test_context,
+        test_context.owner.account_hash,
+        "mint_non_transferable",
+        runtime_args! {
+            "recipient" => Key::Account(seller.account_hash),
+            "token_ids" => vec![TokenId::zero()],
+            "token_metas" => vec![meta::red_dragon()],
+        },
+    );
+
+    // CEP-47 won't approve the market for a soulbound token, so the listing isn't approved.
+    create_market_item(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        Key::Account(seller.account_hash),
+        vec![TokenId::zero()],
+        vec![12345.into()],
+    );
+}
+
+#[test]
+#[should_panic]
+fn should_not_list_token_of_other_owner() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let user = accounts.pop().unwrap();
+    nft_mint(
+        &mut builder,
+        &test_context,
+        test_context.owner.account_hash,
+        seller.account_hash,
+        vec![TokenId::zero()],
+        vec![meta::red_dragon()],
+    );
+    approve_market(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        vec![TokenId::zero()],
+    );
+    create_market_item(
+        &mut builder,
+        &test_context,
+        user.account_hash,
+        Key::Account(user.account_hash),
+        vec![TokenId::zero()],
+        vec![12345.into()],
+    );
+}
+
+#[test]
+fn should_assign_market_item_ids() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let amount: U512 = 12345.into();
+    nft_mint(
+        &mut builder,
+        &test_context,
+        test_context.owner.account_hash,
+        seller.account_hash,
+        vec![TokenId::zero(), TokenId::one()],
+        vec![meta::red_dragon(), meta::blue_dragon()],
+    );
+    approve_market(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        vec![TokenId::zero(), TokenId::one()],
+    );
+
+    create_market_item(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        Key::Account(seller.account_hash),
+        vec![TokenId::zero(), TokenId::one()],
+        vec![amount, amount],
+    );
+    assert_eq!(next_item_id(&mut builder, &test_context), TokenId::from(2));
+    assert_eq!(
+        market_owner_of(&mut builder, &test_context, TokenId::zero()).unwrap(),
+        Key::Account(seller.account_hash)
+    );
+    assert_eq!(
+        market_owner_of(&mut builder, &test_context, TokenId::one()).unwrap(),
+        Key::Account(seller.account_hash)
+    );
+}
+
+#[test]
+fn should_skip_caller_supplied_market_item_ids() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let amount: U512 = 12345.into();
+    nft_mint(
+        &mut builder,
+        &test_context,
+        test_context.owner.account_hash,
+        seller.account_hash,
+        vec![TokenId::zero(), TokenId::one()],
+        vec![meta::red_dragon(), meta::blue_dragon()],
+    );
+    approve_market(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        vec![TokenId::zero(), TokenId::one()],
+    );
+
+    create_market_item_with_ids(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        Key::Account(seller.account_hash),
+        vec![TokenId::zero()],
+        vec![amount],
+    );
+    create_market_item(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        Key::Account(seller.account_hash),
+        vec![TokenId::one()],
+        vec![amount],
+    );
+    assert_eq!(next_item_id(&mut builder, &test_context), TokenId::from(2));
+    assert!(market_owner_of(&mut builder, &test_context, TokenId::one()).is_some());
+}
+
+#[test]
+fn should_pay_collection_royalty_on_sale() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let buyer = accounts.pop().unwrap();
+    let creator = accounts.pop().unwrap();
+    let amount: U512 = 10_000.into();
+
+    let mut collection_meta = meta::contract_meta();
+    collection_meta.insert("royalty_basis_points".to_string(), "500".to_string());
+    collection_meta.insert(
+        "royalty_recipient".to_string(),
+        creator.account_hash.to_formatted_string(),
+    );
+    let test_context = install_collection(
+        &mut builder,
+        test_context,
+        CEP47_WASM,
+        "royalty_collection",
+        collection_meta,
+    );
+    mint_and_list(&mut builder, &test_context, seller.account_hash, amount);
+
+    let seller_balance_before = account_balance(&mut builder, seller.account_hash);
+    let creator_balance_before = account_balance(&mut builder, creator.account_hash);
+    process_market_sale(
+        &mut builder,
+        &test_context,
+        Key::Account(buyer.account_hash),
+        buyer.account_hash,
+        TokenId::zero(),
+        amount,
+    );
+
+    assert_eq!(
+        account_balance(&mut builder, seller.account_hash) - seller_balance_before,
+        U512::from(9_500)
+    );
+    assert_eq!(
+        account_balance(&mut builder, creator.account_hash) - creator_balance_before,
+        U512::from(500)
+    );
+}
+
+#[test]
+fn should_cap_collection_royalty_to_price_left_after_fee() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let buyer = accounts.pop().unwrap();
+    let creator = accounts.pop().unwrap();
+    let fee_recipient = accounts.pop().unwrap();
+    let amount: U512 = 10_000.into();
+
+    let mut collection_meta = meta::contract_meta();
+    collection_meta.insert("royalty_basis_points".to_string(), "9000".to_string());
+    collection_meta.insert(
+        "royalty_recipient".to_string(),
+        creator.account_hash.to_formatted_string(),
+    );
+    let test_context = install_collection(
+        &mut builder,
+        test_context,
+        CEP47_WASM,
+        "greedy_collection",
+        collection_meta,
+    );
+    mint_and_list(&mut builder, &test_context, seller.account_hash, amount);
+    call_market(
+        &mut builder,
+        &test_context,
+        test_context.owner.account_hash,
+        "set_fee_config",
+        runtime_args! {
+            "fee_basis_points" => 2_500u32,
+            "fee_recipient" => Key::Account(fee_recipient.account_hash),
+        },
+    );
+
+    let seller_balance_before = account_balance(&mut builder, seller.account_hash);
+    let creator_balance_before = account_balance(&mut builder, creator.account_hash);
+    process_market_sale(
+        &mut builder,
+        &test_context,
+        Key::Account(buyer.account_hash),
+        buyer.account_hash,
+        TokenId::zero(),
+        amount,
+    );
+
+    assert_eq!(
+        account_balance(&mut builder, creator.account_hash) - creator_balance_before,
+        U512::from(7_500)
+    );
+    assert_eq!(
+        account_balance(&mut builder, seller.account_hash),
+        seller_balance_before
+    );
+}
+
+#[test]
+fn should_pay_seller_in_full_without_collection_royalty() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let buyer = accounts.pop().unwrap();
+    let amount: U512 = 10_000.into();
+
+    mint_and_list(&mut builder, &test_context, seller.account_hash, amount);
+    let seller_balance_before = account_balance(&mut builder, seller.account_hash);
+    process_market_sale(
+        &mut builder,
+        &test_context,
+        Key::Account(buyer.account_hash),
+        buyer.account_hash,
+        TokenId::zero(),
+        amount,
+    );
+
+    assert_eq!(
+        account_balance(&mut builder, seller.account_hash) - seller_balance_before,
+        amount
+    );
+}
+
+#[test]
+fn should_pay_market_fee_on_sale() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let buyer = accounts.pop().unwrap();
+    let fee_recipient = accounts.pop().unwrap();
+    let amount: U512 = 10_000.into();
+
+    nft_mint(
+        &mut builder,
+        &test_context,
+        test_context.owner.account_hash,
+        seller.account_hash,
+        vec![TokenId::zero()],
+        vec![meta::red_dragon()],
+    );
+    approve_market(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        vec![TokenId::zero()],
+    );
+    create_market_item(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        Key::Account(seller.account_hash),
+        vec![TokenId::zero()],
+        vec![amount],
+    );
+    call_market(
+        &mut builder,
+        &test_context,
+        test_context.owner.account_hash,
+        "set_fee_config",
+        runtime_args! {
+            "fee_basis_points" => 250u32,
+            "fee_recipient" => Key::Account(fee_recipient.account_hash),
+        },
+    );
+
+    let seller_balance_before = account_balance(&mut builder, seller.account_hash);
+    let fee_recipient_balance_before = account_balance(&mut builder, fee_recipient.account_hash);
+    process_market_sale(
+        &mut builder,
+        &test_context,
+        Key::Account(buyer.account_hash),
+        buyer.account_hash,
+        TokenId::zero(),
+        amount,
+    );
+
+    assert_eq!(
+        account_balance(&mut builder, seller.account_hash) - seller_balance_before,
+        U512::from(9_750)
+    );
+    assert_eq!(
+        account_balance(&mut builder, fee_recipient.account_hash) - fee_recipient_balance_before,
+        U512::from(250)
+    );
+}
+
+#[test]
+#[should_panic]
+fn should_not_set_fee_config_without_admin_role() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let user = accounts.pop().unwrap();
+    call_market(
+        &mut builder,
+        &test_context,
+        user.account_hash,
+        "set_fee_config",
+        runtime_args! {
+            "fee_basis_points" => 250u32,
+            "fee_recipient" => Key::Account(user.account_hash),
+        },
+    );
+}
+
+#[test]
+#[should_panic]
+fn should_not_set_fee_above_full_price() {
+    let (mut builder, test_context, _) = setup();
+    call_market(
+        &mut builder,
+        &test_context,
+        test_context.owner.account_hash,
+        "set_fee_config",
+        runtime_args! {
+            "fee_basis_points" => 10_001u32,
+            "fee_recipient" => Key::Account(test_context.owner.account_hash),
+        },
+    );
+}
+
+#[ignore]
+#[test]
+fn test_should_fail_sell_market_item_insufficient_funds() {}
+
+#[test]
+#[should_panic]
+fn test_should_fail_sell_market_item_not_available() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let buyer = accounts.pop().unwrap();
+    let amount: U512 = 12345.into();
+    mint_and_list(&mut builder, &test_context, seller.account_hash, amount);
+    call_market(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        "cancel_market_item",
+        runtime_args! { "item_id" => TokenId::zero() },
+    );
+
+    process_market_sale(
+        &mut builder,
+        &test_context,
+        Key::Account(buyer.account_hash),
+        buyer.account_hash,
+        TokenId::zero(),
+        amount,
+    );
+}
+
+#[test]
+fn test_cancel_market_item() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    mint_and_list(&mut builder, &test_context, seller.account_hash, 12345.into());
+
+    call_market(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        "cancel_market_item",
+        runtime_args! { "item_id" => TokenId::zero() },
+    );
+
+    let item = market_item(&mut builder, &test_context, TokenId::zero()).unwrap();
+    assert_eq!(item.status, MarketItemStatus::Cancelled);
+}
+
+#[test]
+#[should_panic]
+fn should_not_cancel_market_item_of_other_seller() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let user = accounts.pop().unwrap();
+    mint_and_list(&mut builder, &test_context, seller.account_hash, 12345.into());
+
+    call_market(
+        &mut builder,
+        &test_context,
+        user.account_hash,
+        "cancel_market_item",
+        runtime_args! { "item_id" => TokenId::zero() },
+    );
+}
+
+#[test]
+fn should_set_item_status_as_admin() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    mint_and_list(&mut builder, &test_context, seller.account_hash, 12345.into());
+
+    call_market(
+        &mut builder,
+        &test_context,
+        test_context.owner.account_hash,
+        "set_item_status",
+        runtime_args! {
+            "item_id" => TokenId::zero(),
+            "status" => MarketItemStatus::Expired as u8,
+        },
+    );
+
+    let item = market_item(&mut builder, &test_context, TokenId::zero()).unwrap();
+    assert_eq!(item.status, MarketItemStatus::Expired);
+}
+
+#[test]
+#[should_panic]
+fn should_not_set_item_status_without_admin_role() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    mint_and_list(&mut builder, &test_context, seller.account_hash, 12345.into());
+
+    call_market(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        "set_item_status",
+        runtime_args! {
+            "item_id" => TokenId::zero(),
+            "status" => MarketItemStatus::Invalid as u8,
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(9)")]
+fn should_not_mark_item_sold_as_admin() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    mint_and_list(&mut builder, &test_context, seller.account_hash, 12345.into());
+
+    call_market(
+        &mut builder,
+        &test_context,
+        test_context.owner.account_hash,
+        "set_item_status",
+        runtime_args! {
+            "item_id" => TokenId::zero(),
+            "status" => MarketItemStatus::Sold as u8,
+        },
+    );
+}
+
+#[test]
+#[should_panic]
+fn should_not_reopen_cancelled_market_item() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    mint_and_list(&mut builder, &test_context, seller.account_hash, 12345.into());
+    call_market(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        "cancel_market_item",
+        runtime_args! { "item_id" => TokenId::zero() },
+    );
+
+    call_market(
+        &mut builder,
+        &test_context,
+        test_context.owner.account_hash,
+        "set_item_status",
+        runtime_args! {
+            "item_id" => TokenId::zero(),
+            "status" => MarketItemStatus::Available as u8,
+        },
+    );
+}
+
+#[test]
+fn should_emit_ces_events() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let buyer = accounts.pop().unwrap();
+    let amount: U512 = 12345.into();
+    assert_eq!(ces_events_length(&mut builder, &test_context), 0);
+
+    mint_and_list(&mut builder, &test_context, seller.account_hash, amount);
+    process_market_sale(
+        &mut builder,
+        &test_context,
+        Key::Account(buyer.account_hash),
+        buyer.account_hash,
+        TokenId::zero(),
+        amount,
+    );
+
+    assert_eq!(ces_events_length(&mut builder, &test_context), 2);
+    let listed = ces_event(&mut builder, &test_context, 0).unwrap();
+    let (name, _) = String::from_bytes(&listed).unwrap();
+    assert_eq!(name, "event_CreateItem");
+    let sold = ces_event(&mut builder, &test_context, 1).unwrap();
+    let (name, rest) = String::from_bytes(&sold).unwrap();
+    let (recipient, rest) = Key::from_bytes(rest).unwrap();
+    let (item_id, rest) = TokenId::from_bytes(rest).unwrap();
+    assert_eq!(name, "event_SoldItem");
+    assert_eq!(recipient, Key::Account(buyer.account_hash));
+    assert_eq!(item_id, TokenId::zero());
+    assert!(rest.is_empty());
+    assert!(ces_event(&mut builder, &test_context, 2).is_none());
+}
+
+#[test]
+fn should_log_listing_and_fee_config_changes() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let fee_recipient = accounts.pop().unwrap();
+    mint_and_list(&mut builder, &test_context, seller.account_hash, 12345.into());
+
+    call_market(
+        &mut builder,
+        &test_context,
+        seller.account_hash,
+        "cancel_market_item",
+        runtime_args! {"item_id" => TokenId::zero()},
+    );
+    call_market(
+        &mut builder,
+        &test_context,
+        test_context.owner.account_hash,
+        "set_fee_config",
+        runtime_args! {
+            "fee_basis_points" => 250u32,
+            "fee_recipient" => Key::Account(fee_recipient.account_hash),
+        },
+    );
+
+    assert_eq!(ces_events_length(&mut builder, &test_context), 3);
+    let cancelled = ces_event(&mut builder, &test_context, 1).unwrap();
+    let (name, rest) = String::from_bytes(&cancelled).unwrap();
+    let (seller_key, rest) = Key::from_bytes(rest).unwrap();
+    let (item_id, rest) = TokenId::from_bytes(rest).unwrap();
+    assert_eq!(name, "event_CancelItem");
+    assert_eq!(seller_key, Key::Account(seller.account_hash));
+    assert_eq!(item_id, TokenId::zero());
+    assert!(rest.is_empty());
+
+    let fee_config = ces_event(&mut builder, &test_context, 2).unwrap();
+    let (name, rest) = String::from_bytes(&fee_config).unwrap();
+    let (fee_basis_points, rest) = u32::from_bytes(rest).unwrap();
+    let (recipient, rest) = Key::from_bytes(rest).unwrap();
+    assert_eq!(name, "event_SetFeeConfig");
+    assert_eq!(fee_basis_points, 250);
+    assert_eq!(recipient, Key::Account(fee_recipient.account_hash));
+    assert!(rest.is_empty());
+}
+
+#[test]
+fn should_invalidate_listing_of_burned_token() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let anyone = accounts.pop().unwrap();
+    mint_and_list(&mut builder, &test_context, seller.account_hash, 12345.into());
+    admin_burn(&mut builder, &test_context, vec![TokenId::zero()]);
+
+    call_market(
+        &mut builder,
+        &test_context,
+        anyone.account_hash,
+        "invalidate_market_item",
+        runtime_args! {"item_id" => TokenId::zero()},
+    );
+
+    let item = market_item(&mut builder, &test_context, TokenId::zero()).unwrap();
+    assert_eq!(item.status, MarketItemStatus::Invalid);
+    assert_eq!(active_items_count(&mut builder, &test_context), U256::zero());
+    let invalidated = ces_event(&mut builder, &test_context, 1).unwrap();
+    let (name, rest) = String::from_bytes(&invalidated).unwrap();
+    let (item_id, _) = TokenId::from_bytes(rest).unwrap();
+    assert_eq!(name, "event_InvalidateItem");
+    assert_eq!(item_id, TokenId::zero());
+}
+
+#[test]
+#[should_panic]
+fn should_not_sell_listing_of_burned_token() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let buyer = accounts.pop().unwrap();
+    let amount: U512 = 12345.into();
+    mint_and_list(&mut builder, &test_context, seller.account_hash, amount);
+    admin_burn(&mut builder, &test_context, vec![TokenId::zero()]);
+
+    process_market_sale(
+        &mut builder,
+        &test_context,
+        Key::Account(buyer.account_hash),
+        buyer.account_hash,
+        TokenId::zero(),
+        amount,
+    );
+}
+
+#[test]
+#[should_panic]
+fn should_not_invalidate_backed_listing() {
+    let (mut builder, test_context, mut accounts) = setup();
+    let seller = accounts.pop().unwrap();
+    let anyone = accounts.pop().unwrap();
+    mint_and_list(&mut builder, &test_context, seller.account_hash, 12345.into());
+
+    call_market(
+        &mut builder,
+        &test_context,
+        anyone.account_hash,
+        "invalidate_market_item",
+        runtime_args! {"item_id" => TokenId::zero()},
+    );
+}
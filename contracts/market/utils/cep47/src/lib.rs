@@ -1,3 +1,6 @@
+//! The plain CEP-47 interface the market calls into. Collections that also implement operator
+//! approvals or non-transferable tokens are flagged per collection through
+//! `set_collection_capabilities`; the market only calls those entry points when flagged.
 #![no_std]
 #[macro_use]
 extern crate alloc;
@@ -0,0 +1,143 @@
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    bytesrepr::{self, Bytes, ToBytes},
+    CLType, CLTyped, URef,
+};
+
+use crate::{Dict, get_key, set_key};
+
+const EVENTS_DICT: &str = "__events";
+const EVENTS_LENGTH: &str = "__events_length";
+const EVENTS_SCHEMA: &str = "__events_schema";
+const CES_VERSION_KEY: &str = "__events_ces_version";
+const CES_VERSION: &str = "0.1.0";
+const EVENT_PREFIX: &str = "event_";
+
+/// Named, typed fields of one event, in the order they are serialized.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Schema(Vec<(String, CLType)>);
+
+impl Schema {
+    pub fn new() -> Schema {
+        Schema::default()
+    }
+
+    pub fn with_elem<T: CLTyped>(mut self, name: &str) -> Schema {
+        self.0.push((name.to_string(), T::cl_type()));
+        self
+    }
+}
+
+/// A list of `(name, type)` pairs. The field types are typed `Any`, as in casper-event-standard,
+/// since `CLType` has no CL type of its own.
+impl CLTyped for Schema {
+    fn cl_type() -> CLType {
+        CLType::List(Box::new(CLType::Tuple2([
+            Box::new(CLType::String),
+            Box::new(CLType::Any),
+        ])))
+    }
+}
+
+impl ToBytes for Schema {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        self.0.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.0.serialized_length()
+    }
+}
+
+/// Schemas of every event a contract emits, keyed by event name.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Schemas(BTreeMap<String, Schema>);
+
+impl Schemas {
+    pub fn new() -> Schemas {
+        Schemas::default()
+    }
+
+    pub fn with(mut self, event_name: &str, schema: Schema) -> Schemas {
+        self.0.insert(event_name.to_string(), schema);
+        self
+    }
+}
+
+impl CLTyped for Schemas {
+    fn cl_type() -> CLType {
+        BTreeMap::<String, Schema>::cl_type()
+    }
+}
+
+impl ToBytes for Schemas {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        self.0.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.0.serialized_length()
+    }
+}
+
+/// The contracts' event log, kept in the Casper Event Standard layout so generic explorers and
+/// indexers decode it using the schemas in `__events_schema`.
+///
+/// Records go into the `__events` dictionary under consecutive indexes from 0, and
+/// `__events_length` holds the next one, so indexers replay history from any offset with plain
+/// global-state queries. Each record is the event name prefixed with `event_`, followed by the
+/// fields listed in its schema; `__events_ces_version` versions that format.
+pub struct CesEvents {
+    uref: URef,
+}
+
+impl CesEvents {
+    pub fn instance() -> CesEvents {
+        let key = runtime::get_key(EVENTS_DICT).unwrap_or_revert();
+        CesEvents {
+            uref: *key.as_uref().unwrap_or_revert(),
+        }
+    }
+
+    /// Creates the event storage and publishes `schemas`. Safe to call again on upgrade, in
+    /// which case only the schemas are replaced.
+    pub fn init(schemas: Schemas) {
+        if !runtime::has_key(EVENTS_DICT) {
+            Dict::init(EVENTS_DICT);
+            set_key(EVENTS_LENGTH, 0u32);
+        }
+        set_key(EVENTS_SCHEMA, schemas);
+        set_key(CES_VERSION_KEY, String::from(CES_VERSION));
+    }
+
+    pub fn len(&self) -> u32 {
+        get_key(EVENTS_LENGTH).unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Records `fields`, the bytesrepr encoding of an event registered as `event_name`.
+    pub fn emit<T: ToBytes>(&self, event_name: &str, fields: &T) {
+        let index = self.len();
+        let mut bytes = [EVENT_PREFIX, event_name]
+            .concat()
+            .to_bytes()
+            .unwrap_or_revert();
+        bytes.append(&mut fields.to_bytes().unwrap_or_revert());
+        // Records are stored bare rather than as `Option`s, as the standard expects.
+        storage::dictionary_put(self.uref, &index.to_string(), Bytes::from(bytes));
+        set_key(EVENTS_LENGTH, index + 1);
+    }
+}
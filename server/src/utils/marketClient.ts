@@ -1,7 +1,11 @@
 import {
   CasperClient,
+  CLKey,
   CLMap,
+  CLOption,
   CLPublicKey,
+  CLTuple2,
+  CLTuple3,
   CLTypeTag,
   CLValue,
   CLValueBuilder,
@@ -88,6 +92,49 @@ export const MARKETEventParser = (
 //   return hex;
 // };
 
+export const MARKET_ITEM_STATUSES = [
+  "available",
+  "sold",
+  "cancelled",
+  "expired",
+  "invalid",
+  "in_auction",
+];
+
+export interface MarketItemRecord {
+  itemId: string;
+  seller: string;
+  nftContractAddress: string;
+  tokenId: string;
+  askingPrice: string;
+  status: string;
+}
+
+// Decodes the `Option<MarketItem>` stored in the `market_items` dictionary,
+// using the `((item_id, seller, nft_contract_address), (token_id,
+// asking_price, status))` tuple type the contract declares for it.
+export const parseMarketItem = (storedValue: any): MarketItemRecord | null => {
+  const maybeItem = CLValueParsers.fromJSON(storedValue).unwrap() as CLOption<
+    CLTuple2
+  >;
+  const item = maybeItem.value();
+  if (item.none) {
+    return null;
+  }
+  const [listing, sale] = item.val.value() as [CLTuple3, CLTuple3];
+  const [itemId, seller, nftContractAddress] = listing.value();
+  const [tokenId, askingPrice, status] = sale.value();
+
+  return {
+    itemId: itemId.value().toString(),
+    seller: Buffer.from((seller as CLKey).value().value()).toString("hex"),
+    nftContractAddress: Buffer.from(nftContractAddress.value()).toString("hex"),
+    tokenId: tokenId.value().toString(),
+    askingPrice: askingPrice.value().toString(),
+    status: MARKET_ITEM_STATUSES[status.value().toNumber()],
+  };
+};
+
 export interface MarketItem extends NFT {
   isApproved: boolean;
   available: boolean;
@@ -159,21 +206,28 @@ export class MarketClient {
     return values;
   }
 
-  public async getMarketItemStatus(itemId: string) {
-    const result = await this.contractClient.queryContractDictionary(
-      "item_statuses",
-      itemId
+  public async getMarketItem(itemId: string) {
+    const stateRootHash = await this.casperClient.nodeClient.getStateRootHash();
+    const result = await this.casperClient.nodeClient.getDictionaryItemByName(
+      stateRootHash,
+      // @ts-ignore
+      this.contractClient.contractHash,
+      "market_items",
+      itemId,
+      { rawData: true }
     );
-    return result.value().unwrap().data;
+    // @ts-ignore
+    return parseMarketItem(result.stored_value.CLValue);
+  }
+
+  public async getMarketItemStatus(itemId: string) {
+    const item = await this.getMarketItem(itemId);
+    return item?.status;
   }
 
   public async getMarketItemPrice(itemId: string) {
-    const result = await this.contractClient.queryContractDictionary(
-      "item_asking_prices",
-      itemId
-    );
-    const value = result.value().unwrap();
-    return value.data.toNumber();
+    const item = await this.getMarketItem(itemId);
+    return item?.askingPrice;
   }
 
   public async marketItemHash() {
@@ -231,7 +285,7 @@ export class MarketClient {
     });
 
     return this.contractClient.callEntrypoint(
-      "create_market_item",
+      "create_market_item_with_ids",
       runtimeArgs,
       deploySender,
       this.networkName,
@@ -277,6 +277,10 @@ export function getMarketItem(item: NFT): Promise<MarketItem | null> {
         // @ts-ignore
         const askingPrice = await marketClient.getMarketItemPrice(lastItem);
         console.log({ askingPrice });
+        if (status === undefined || askingPrice === undefined) {
+          resolve(null);
+          return;
+        }
 
         // @ts-ignore
         const approvalHash = await marketClient.marketItemHash();